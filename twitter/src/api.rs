@@ -1,12 +1,16 @@
+use std::time::{Duration, SystemTime};
+
 use crate::{
     errors::DatabaseError,
-    misc::Identifier,
-    models::{Follow, Tweet},
+    misc::{Identifier, UnixTimestamp},
+    models::{Conversation, Follow, List, Message, ScheduledTweet, Trend, Tweet, User},
     repo::TwitterRepository,
     storage::{DatabaseRef, Transaction},
     structures::SimpleTransaction,
+    GLOBAL_BACKFILL_SIZE, GLOBAL_PAGE_SIZE,
 };
 
+#[derive(Clone)]
 pub struct TwitterApi {
     pub repo: TwitterRepository,
 }
@@ -20,33 +24,154 @@ impl TwitterApi {
 
     #[allow(dead_code)]
     pub async fn batch_create_follows(
-        &mut self,
+        &self,
         f: Vec<Follow>,
         save: bool,
     ) -> Result<(), DatabaseError> {
         let tx = &mut self.repo.mut_tx().await;
+        if save {
+            // Stream the follows in one go rather than insert them one by one
+            self.repo.bulk_create_follows(tx, f).await?;
+            tx.commit().await?;
+            return Ok(());
+        }
         for follow in f {
             self.repo
-                .create_follow(tx, follow.from(), follow.to())
+                .create_follow(tx, follow.from(), follow.to(), None)
                 .await?;
         }
-        if save {
-            tx.commit().await?;
-        }
         Ok(())
     }
 
-    pub async fn post_tweet(
-        &mut self,
-        t: Tweet,
+    /// Follows of protected accounts become pending requests
+    #[allow(dead_code)]
+    pub async fn follow(
+        &self,
+        from: Identifier,
+        to: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo
+            .create_follow(tx, from, to, Some(GLOBAL_BACKFILL_SIZE))
+            .await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn unfollow(
+        &self,
+        from: Identifier,
+        to: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo.delete_follow(tx, from, to).await?;
+        Ok(())
+    }
+
+    /// Pending follow requests of a protected account, oldest first
+    #[allow(dead_code)]
+    pub async fn get_follow_requests(
+        &self,
+        owner: Identifier,
+        tx: &Transaction,
+    ) -> Result<Vec<Follow>, DatabaseError> {
+        let requests = self.repo.get_follow_requests(tx, owner).await?;
+        Ok(requests)
+    }
+
+    #[allow(dead_code)]
+    pub async fn approve_follow_request(
+        &self,
+        owner: Identifier,
+        requester: Identifier,
         tx: &mut Transaction,
     ) -> Result<(), DatabaseError> {
+        self.repo
+            .approve_follow_request(tx, requester, owner, Some(GLOBAL_BACKFILL_SIZE))
+            .await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn reject_follow_request(
+        &self,
+        owner: Identifier,
+        requester: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo
+            .reject_follow_request(tx, requester, owner)
+            .await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn bulk_load_tweets(&self, t: Vec<Tweet>) -> Result<(), DatabaseError> {
+        let tx = &mut self.repo.mut_tx().await;
+        self.repo.bulk_create_tweets(tx, t).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn post_tweet(&self, t: Tweet, tx: &mut Transaction) -> Result<(), DatabaseError> {
         self.repo.create_tweet(tx, t.author(), t.tweet_text).await?;
         Ok(())
     }
 
+    #[allow(dead_code)]
+    pub async fn reply_to_tweet(
+        &self,
+        t: Tweet,
+        in_reply_to: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo
+            .create_reply(tx, t.author(), t.tweet_text, in_reply_to)
+            .await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn quote_tweet(
+        &self,
+        t: Tweet,
+        quoted_tweet_id: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo
+            .create_quote(tx, t.author(), t.tweet_text, quoted_tweet_id)
+            .await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_conversation(
+        &self,
+        tweet_id: Identifier,
+        max_depth: i32,
+        viewer: Identifier,
+        tx: &Transaction,
+    ) -> Result<Vec<Tweet>, DatabaseError> {
+        let tweets = self
+            .repo
+            .get_conversation(tx, tweet_id, max_depth, viewer)
+            .await?;
+        Ok(tweets)
+    }
+
+    #[allow(dead_code)]
+    pub async fn delete_tweet(
+        &self,
+        tweet_id: Identifier,
+        author: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo.delete_tweet(tx, tweet_id, author).await?;
+        Ok(())
+    }
+
     pub async fn batch_post_tweets(
-        &mut self,
+        &self,
         t: Vec<Tweet>,
         tx: &mut Transaction,
     ) -> Result<(), DatabaseError> {
@@ -55,11 +180,473 @@ impl TwitterApi {
     }
 
     pub async fn get_timeline(
-        &mut self,
+        &self,
         user_id: Identifier,
         tx: &Transaction,
     ) -> Result<Vec<Tweet>, DatabaseError> {
         let tweets = self.repo.get_timeline(tx, user_id).await?;
         Ok(tweets)
     }
+
+    #[allow(dead_code)]
+    pub async fn get_mentions_timeline(
+        &self,
+        user_id: Identifier,
+        tx: &Transaction,
+    ) -> Result<Vec<Tweet>, DatabaseError> {
+        let tweets = self.repo.get_mentions_timeline(tx, user_id).await?;
+        Ok(tweets)
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_hashtag_timeline(
+        &self,
+        tag: String,
+        viewer: Identifier,
+        tx: &Transaction,
+    ) -> Result<Vec<Tweet>, DatabaseError> {
+        let tweets = self.repo.get_hashtag_timeline(tx, tag, viewer).await?;
+        Ok(tweets)
+    }
+
+    /// Returns a page of matching tweets and the cursor of the next page, if any
+    #[allow(dead_code)]
+    pub async fn search(
+        &self,
+        query: String,
+        limit: i64,
+        cursor: Option<i64>,
+        viewer: Identifier,
+        tx: &Transaction,
+    ) -> Result<(Vec<Tweet>, Option<i64>), DatabaseError> {
+        let offset = cursor.unwrap_or_default();
+        let tweets = self
+            .repo
+            .search_tweets(tx, query, limit, offset, viewer)
+            .await?;
+        let next_cursor = (tweets.len() as i64 == limit).then_some(offset + limit);
+        Ok((tweets, next_cursor))
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_trending(
+        &self,
+        window: Duration,
+        limit: i64,
+        tx: &Transaction,
+    ) -> Result<Vec<Trend>, DatabaseError> {
+        let trends = self
+            .repo
+            .get_trending(tx, window.as_secs() as i64, limit)
+            .await?;
+        Ok(trends)
+    }
+
+    #[allow(dead_code)]
+    pub async fn create_user(&self, u: User, tx: &mut Transaction) -> Result<(), DatabaseError> {
+        self.repo.create_user(tx, u).await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn update_user(&self, u: User, tx: &mut Transaction) -> Result<(), DatabaseError> {
+        self.repo.update_user(tx, u).await?;
+        Ok(())
+    }
+
+    /// Making the account public approves its pending follow requests
+    #[allow(dead_code)]
+    pub async fn set_protected(
+        &self,
+        user_id: Identifier,
+        protected: bool,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo
+            .set_user_protected(tx, user_id, protected, Some(GLOBAL_BACKFILL_SIZE))
+            .await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_user(
+        &self,
+        user_id: Identifier,
+        tx: &Transaction,
+    ) -> Result<Option<User>, DatabaseError> {
+        let user = self.repo.get_user(tx, user_id).await?;
+        Ok(user)
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_user_by_handle(
+        &self,
+        handle: String,
+        tx: &Transaction,
+    ) -> Result<Option<User>, DatabaseError> {
+        let user = self.repo.get_user_by_handle(tx, handle).await?;
+        Ok(user)
+    }
+
+    /// Returns up to `limit` users whose handle starts with the prefix, most followed first.
+    /// Only the first `max(50, limit)` matching handles in lexicographic order are ranked, so for
+    /// short prefixes a more followed user further down the alphabet may be left out
+    #[allow(dead_code)]
+    pub async fn autocomplete_users(
+        &self,
+        prefix: String,
+        limit: i64,
+        tx: &Transaction,
+    ) -> Result<Vec<User>, DatabaseError> {
+        let users = self.repo.autocomplete_users(tx, prefix, limit).await?;
+        Ok(users)
+    }
+
+    #[allow(dead_code)]
+    pub async fn retweet(
+        &self,
+        user_id: Identifier,
+        tweet_id: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo.create_retweet(tx, user_id, tweet_id).await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn like(
+        &self,
+        user_id: Identifier,
+        tweet_id: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo.create_like(tx, user_id, tweet_id).await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn unlike(
+        &self,
+        user_id: Identifier,
+        tweet_id: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo.delete_like(tx, user_id, tweet_id).await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn like_count(
+        &self,
+        tweet_id: Identifier,
+        tx: &Transaction,
+    ) -> Result<i64, DatabaseError> {
+        let count = self.repo.get_like_count(tx, tweet_id).await?;
+        Ok(count)
+    }
+
+    #[allow(dead_code)]
+    pub async fn liked_by(
+        &self,
+        tweet_id: Identifier,
+        limit: i64,
+        offset: i64,
+        tx: &Transaction,
+    ) -> Result<Vec<Identifier>, DatabaseError> {
+        let users = self.repo.get_liked_by(tx, tweet_id, limit, offset).await?;
+        Ok(users)
+    }
+
+    #[allow(dead_code)]
+    pub async fn send_message(
+        &self,
+        sender_id: Identifier,
+        recipient_id: Identifier,
+        text: String,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo
+            .create_message(tx, sender_id, recipient_id, text)
+            .await?;
+        Ok(())
+    }
+
+    /// Conversations of the user, most recently active first
+    #[allow(dead_code)]
+    pub async fn get_conversations(
+        &self,
+        user_id: Identifier,
+        limit: i64,
+        tx: &Transaction,
+    ) -> Result<Vec<Conversation>, DatabaseError> {
+        let conversations = self.repo.get_conversations(tx, user_id, limit).await?;
+        Ok(conversations)
+    }
+
+    /// Returns a page of the messages between two users, newest first, and the cursor of the
+    /// next page, if any. The cursor is a message id so new messages don't shift the pages
+    #[allow(dead_code)]
+    pub async fn get_messages(
+        &self,
+        user_id: Identifier,
+        other_id: Identifier,
+        limit: i64,
+        cursor: Option<Identifier>,
+        tx: &Transaction,
+    ) -> Result<(Vec<Message>, Option<Identifier>), DatabaseError> {
+        let messages = self
+            .repo
+            .get_messages(
+                tx,
+                user_id,
+                other_id,
+                cursor.unwrap_or(Identifier::MAX),
+                limit,
+            )
+            .await?;
+        let next_cursor = match messages.last() {
+            Some(last) if messages.len() as i64 == limit => Some(last.message_id),
+            _ => None,
+        };
+        Ok((messages, next_cursor))
+    }
+
+    /// Blocking also removes the follows in both directions
+    #[allow(dead_code)]
+    pub async fn block(
+        &self,
+        user_id: Identifier,
+        blocked_id: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo.create_block(tx, user_id, blocked_id).await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn unblock(
+        &self,
+        user_id: Identifier,
+        blocked_id: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo.delete_block(tx, user_id, blocked_id).await?;
+        Ok(())
+    }
+
+    /// Hides the muted user's tweets and retweets from the home timeline, the follow is kept
+    #[allow(dead_code)]
+    pub async fn mute(
+        &self,
+        user_id: Identifier,
+        muted_id: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo.create_mute(tx, user_id, muted_id).await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn unmute(
+        &self,
+        user_id: Identifier,
+        muted_id: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo
+            .delete_mute(tx, user_id, muted_id, Some(GLOBAL_BACKFILL_SIZE))
+            .await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn bookmark(
+        &self,
+        user_id: Identifier,
+        tweet_id: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo.create_bookmark(tx, user_id, tweet_id).await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn unbookmark(
+        &self,
+        user_id: Identifier,
+        tweet_id: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo.delete_bookmark(tx, user_id, tweet_id).await?;
+        Ok(())
+    }
+
+    /// Returns a page of the user bookmarks, newest bookmark first, and the cursor of the next
+    /// page, if any. Bookmarks of deleted tweets are dropped with the tweet
+    #[allow(dead_code)]
+    pub async fn get_bookmarks(
+        &self,
+        user_id: Identifier,
+        cursor: Option<i64>,
+        tx: &Transaction,
+    ) -> Result<(Vec<Tweet>, Option<i64>), DatabaseError> {
+        let offset = cursor.unwrap_or_default();
+        let tweets = self
+            .repo
+            .get_bookmarks(tx, user_id, GLOBAL_PAGE_SIZE, offset)
+            .await?;
+        let next_cursor =
+            (tweets.len() as i64 == GLOBAL_PAGE_SIZE).then_some(offset + GLOBAL_PAGE_SIZE);
+        Ok((tweets, next_cursor))
+    }
+
+    #[allow(dead_code)]
+    pub async fn create_list(
+        &self,
+        owner: Identifier,
+        list_name: String,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo.create_list(tx, owner, list_name).await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn delete_list(
+        &self,
+        owner: Identifier,
+        list_name: String,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo.delete_list(tx, owner, list_name).await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_lists(
+        &self,
+        owner: Identifier,
+        tx: &Transaction,
+    ) -> Result<Vec<List>, DatabaseError> {
+        let lists = self.repo.get_lists(tx, owner).await?;
+        Ok(lists)
+    }
+
+    #[allow(dead_code)]
+    pub async fn add_list_member(
+        &self,
+        owner: Identifier,
+        list_name: String,
+        member: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo
+            .create_list_member(tx, owner, list_name, member, Some(GLOBAL_BACKFILL_SIZE))
+            .await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn remove_list_member(
+        &self,
+        owner: Identifier,
+        list_name: String,
+        member: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo
+            .delete_list_member(tx, owner, list_name, member)
+            .await?;
+        Ok(())
+    }
+
+    /// Newest tweets of the list members, the list equivalent of the home timeline
+    #[allow(dead_code)]
+    pub async fn get_list_timeline(
+        &self,
+        owner: Identifier,
+        list_name: String,
+        tx: &Transaction,
+    ) -> Result<Vec<Tweet>, DatabaseError> {
+        let tweets = self.repo.get_list_timeline(tx, owner, list_name).await?;
+        Ok(tweets)
+    }
+
+    /// Stores the tweet until `publish_at`, it stays out of every timeline until the scheduler
+    /// publishes it
+    #[allow(dead_code)]
+    pub async fn schedule_tweet(
+        &self,
+        t: Tweet,
+        publish_at: UnixTimestamp,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        if publish_at <= SystemTime::now() {
+            return Err(DatabaseError::TxConditionNotMet);
+        }
+        self.repo
+            .create_scheduled_tweet(tx, t.author(), t.tweet_text, publish_at)
+            .await?;
+        Ok(())
+    }
+
+    /// Pending scheduled tweets of the user, soonest first
+    #[allow(dead_code)]
+    pub async fn get_scheduled_tweets(
+        &self,
+        user_id: Identifier,
+        tx: &Transaction,
+    ) -> Result<Vec<ScheduledTweet>, DatabaseError> {
+        let tweets = self.repo.get_scheduled_tweets(tx, user_id).await?;
+        Ok(tweets)
+    }
+
+    #[allow(dead_code)]
+    pub async fn cancel_scheduled_tweet(
+        &self,
+        user_id: Identifier,
+        scheduled_id: Identifier,
+        tx: &mut Transaction,
+    ) -> Result<(), DatabaseError> {
+        self.repo
+            .delete_scheduled_tweet(tx, user_id, scheduled_id)
+            .await?;
+        Ok(())
+    }
+
+    /// Posts the scheduled tweets whose time has come through the regular tweet path, so they
+    /// are fanned out like any other tweet, a page at a time until none is due. Returns the
+    /// number of tweets published
+    pub async fn publish_due_tweets(&self) -> Result<usize, DatabaseError> {
+        let mut published = 0;
+        loop {
+            let tx = &mut self.repo.mut_tx().await;
+            let due = self
+                .repo
+                .get_due_scheduled_tweets(tx, SystemTime::now(), GLOBAL_PAGE_SIZE)
+                .await?;
+            let claimed = due.len();
+            for scheduled in due {
+                // Taking the claimed tweet before posting it means a scheduler claiming it again
+                // after a timeout never posts it twice, it is skipped once taken
+                match self
+                    .repo
+                    .take_scheduled_tweet(tx, scheduled.user_id, scheduled.scheduled_id)
+                    .await
+                {
+                    Err(DatabaseError::TxnKeyNotFound) => continue,
+                    result => result?,
+                }
+                self.repo
+                    .create_tweet(tx, scheduled.user_id, scheduled.tweet_text)
+                    .await?;
+                published += 1;
+            }
+            tx.commit().await?;
+            if (claimed as i64) < GLOBAL_PAGE_SIZE {
+                break;
+            }
+        }
+        Ok(published)
+    }
 }
@@ -0,0 +1,47 @@
+use crate::utils::{get_absolute_path, read_file_string};
+use once_cell::sync::Lazy;
+use sha2::{Digest, Sha256};
+
+/// ## Migration
+/// Versioned schema change loaded from `src/migrations`. Every migration is a
+/// pair of `{version}_{name}.up.sql` and `{version}_{name}.down.sql` files,
+/// applied in ascending version order.
+#[derive(Clone, Debug)]
+pub struct Migration {
+    pub version: i32,
+    pub name: String,
+    pub up: String,
+    pub down: String,
+    pub checksum: String,
+}
+
+fn load_migrations(dir: &'static str) -> Vec<Migration> {
+    let mut migrations = vec![];
+    for entry in std::fs::read_dir(get_absolute_path(dir)).unwrap() {
+        let file_name = entry.unwrap().file_name().into_string().unwrap();
+        let stem = match file_name.strip_suffix(".up.sql") {
+            Some(stem) => stem.to_string(),
+            None => continue,
+        };
+        let (version, name) = stem.split_once('_').unwrap();
+        let up = read_file_string(&format!("{}/{}", dir, file_name)).unwrap();
+        let down = read_file_string(&format!("{}/{}.down.sql", dir, stem)).unwrap();
+
+        migrations.push(Migration {
+            version: version.parse::<i32>().unwrap(),
+            name: name.to_string(),
+            checksum: format!("{:x}", Sha256::digest(up.as_bytes())),
+            up,
+            down,
+        });
+    }
+    migrations.sort_by_key(|m| m.version);
+    for pair in migrations.windows(2) {
+        assert_ne!(pair[0].version, pair[1].version, "duplicate migration");
+    }
+
+    migrations
+}
+
+pub static GLOBAL_MIGRATIONS: Lazy<Vec<Migration>> =
+    Lazy::new(|| load_migrations("./src/migrations"));
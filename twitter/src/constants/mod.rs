@@ -1,3 +1,5 @@
+mod migrations;
 mod sql;
 
+pub use migrations::*;
 pub use sql::*;
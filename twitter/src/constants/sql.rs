@@ -5,8 +5,7 @@ use std::collections::HashMap;
 
 fn load_script(script: &'static str) -> String {
     let path = &format!("./src/queries/{}.sql", script).to_string();
-    let query = read_file_string(path).unwrap();
-    query
+    read_file_string(path).unwrap()
 }
 
 pub fn scriptify(doc: Document, event: SQLEvent) -> String {
@@ -24,30 +23,33 @@ pub fn get_sql_script(doc: Document, method: SQLEvent) -> String {
     script.clone()
 }
 
+pub fn has_sql_script(doc: Document, method: SQLEvent) -> bool {
+    GLOBAL_SQL_SCRIPTS.contains_key(&scriptify(doc, method))
+}
+
 pub static GLOBAL_SQL_SCRIPTS: Lazy<HashMap<String, String>> = Lazy::new(|| {
     let mut scripts = HashMap::new();
     // General scripts
     scripts.insert(
-        scriptify(Document::GENERAL, SQLEvent::Reset),
-        load_script("reset"),
+        scriptify(
+            Document::GENERAL,
+            SQLEvent::CreateTable("SchemaMigrations".to_string()),
+        ),
+        load_script("create_table_schema_migrations"),
     );
+
+    // Schema migrations script
     scripts.insert(
-        scriptify(Document::GENERAL, SQLEvent::CreateIndices),
-        load_script("create_indices"),
+        scriptify(Document::SchemaMigrations, SQLEvent::Insert),
+        load_script("insert_schema_migration"),
     );
     scripts.insert(
-        scriptify(
-            Document::GENERAL,
-            SQLEvent::CreateTable("Tweets".to_string()),
-        ),
-        load_script("create_table_tweets"),
+        scriptify(Document::SchemaMigrations, SQLEvent::Delete),
+        load_script("delete_schema_migration"),
     );
     scripts.insert(
-        scriptify(
-            Document::GENERAL,
-            SQLEvent::CreateTable("Follows".to_string()),
-        ),
-        load_script("create_table_follows"),
+        scriptify(Document::SchemaMigrations, SQLEvent::Select("applied")),
+        load_script("select_schema_migrations"),
     );
 
     // Tweets script
@@ -59,15 +61,221 @@ pub static GLOBAL_SQL_SCRIPTS: Lazy<HashMap<String, String>> = Lazy::new(|| {
         scriptify(Document::Tweets, SQLEvent::BatchInsert),
         load_script("batch_insert_tweets"),
     );
+    scripts.insert(
+        scriptify(Document::Tweets, SQLEvent::Copy),
+        load_script("copy_tweets"),
+    );
     scripts.insert(
         scriptify(Document::Tweets, SQLEvent::Select("user_timeline")),
         load_script("select_user_timeline"),
     );
+    scripts.insert(
+        scriptify(Document::Tweets, SQLEvent::InsertBy("reply")),
+        load_script("insert_reply"),
+    );
+    scripts.insert(
+        scriptify(Document::Tweets, SQLEvent::InsertBy("quote")),
+        load_script("insert_quote"),
+    );
+    scripts.insert(
+        scriptify(Document::Tweets, SQLEvent::Select("conversation")),
+        load_script("select_conversation"),
+    );
+    scripts.insert(
+        scriptify(Document::Tweets, SQLEvent::Select("mentions_timeline")),
+        load_script("select_mentions_timeline"),
+    );
+    scripts.insert(
+        scriptify(Document::Tweets, SQLEvent::Select("hashtag_timeline")),
+        load_script("select_hashtag_timeline"),
+    );
+    scripts.insert(
+        scriptify(Document::Tweets, SQLEvent::Select("search")),
+        load_script("select_search"),
+    );
+    scripts.insert(
+        scriptify(Document::Tweets, SQLEvent::Delete),
+        load_script("delete_tweet"),
+    );
 
     // Follows script
     scripts.insert(
         scriptify(Document::Follows, SQLEvent::Insert),
         load_script("insert_follow"),
     );
+    scripts.insert(
+        scriptify(Document::Follows, SQLEvent::Copy),
+        load_script("copy_follows"),
+    );
+    scripts.insert(
+        scriptify(Document::Follows, SQLEvent::Delete),
+        load_script("delete_follow"),
+    );
+    scripts.insert(
+        scriptify(Document::Follows, SQLEvent::Update("approve")),
+        load_script("update_follow_approve"),
+    );
+    scripts.insert(
+        scriptify(Document::Follows, SQLEvent::DeleteBy("request")),
+        load_script("delete_follow_request"),
+    );
+    scripts.insert(
+        scriptify(Document::Follows, SQLEvent::Select("requests")),
+        load_script("select_follow_requests"),
+    );
+
+    // Likes script
+    scripts.insert(
+        scriptify(Document::Likes, SQLEvent::Insert),
+        load_script("insert_like"),
+    );
+    scripts.insert(
+        scriptify(Document::Likes, SQLEvent::Delete),
+        load_script("delete_like"),
+    );
+    scripts.insert(
+        scriptify(Document::Likes, SQLEvent::Select("like_count")),
+        load_script("select_like_count"),
+    );
+    scripts.insert(
+        scriptify(Document::Likes, SQLEvent::Select("liked_by")),
+        load_script("select_liked_by"),
+    );
+
+    // Retweets script
+    scripts.insert(
+        scriptify(Document::Retweets, SQLEvent::Insert),
+        load_script("insert_retweet"),
+    );
+
+    // Hashtags script
+    scripts.insert(
+        scriptify(Document::Hashtags, SQLEvent::Select("trending")),
+        load_script("select_trending"),
+    );
+
+    // Users script
+    scripts.insert(
+        scriptify(Document::Users, SQLEvent::Insert),
+        load_script("insert_user"),
+    );
+    scripts.insert(
+        scriptify(Document::Users, SQLEvent::Update("profile")),
+        load_script("update_user_profile"),
+    );
+    scripts.insert(
+        scriptify(Document::Users, SQLEvent::Update("protected")),
+        load_script("update_user_protected"),
+    );
+    scripts.insert(
+        scriptify(Document::Users, SQLEvent::Select("user")),
+        load_script("select_user"),
+    );
+    scripts.insert(
+        scriptify(Document::Users, SQLEvent::Select("user_by_handle")),
+        load_script("select_user_by_handle"),
+    );
+    scripts.insert(
+        scriptify(Document::Users, SQLEvent::Select("autocomplete")),
+        load_script("select_autocomplete_users"),
+    );
+
+    // Messages script
+    scripts.insert(
+        scriptify(Document::Messages, SQLEvent::Insert),
+        load_script("insert_message"),
+    );
+    scripts.insert(
+        scriptify(Document::Messages, SQLEvent::Select("conversations")),
+        load_script("select_conversations"),
+    );
+    scripts.insert(
+        scriptify(Document::Messages, SQLEvent::Select("messages")),
+        load_script("select_messages"),
+    );
+
+    // Bookmarks script
+    scripts.insert(
+        scriptify(Document::Bookmarks, SQLEvent::Insert),
+        load_script("insert_bookmark"),
+    );
+    scripts.insert(
+        scriptify(Document::Bookmarks, SQLEvent::Delete),
+        load_script("delete_bookmark"),
+    );
+    scripts.insert(
+        scriptify(Document::Bookmarks, SQLEvent::Select("bookmarks")),
+        load_script("select_bookmarks"),
+    );
+
+    // Lists script
+    scripts.insert(
+        scriptify(Document::Lists, SQLEvent::Insert),
+        load_script("insert_list"),
+    );
+    scripts.insert(
+        scriptify(Document::Lists, SQLEvent::Delete),
+        load_script("delete_list"),
+    );
+    scripts.insert(
+        scriptify(Document::Lists, SQLEvent::Select("lists")),
+        load_script("select_lists"),
+    );
+    scripts.insert(
+        scriptify(Document::Lists, SQLEvent::InsertBy("member")),
+        load_script("insert_list_member"),
+    );
+    scripts.insert(
+        scriptify(Document::Lists, SQLEvent::DeleteBy("member")),
+        load_script("delete_list_member"),
+    );
+    scripts.insert(
+        scriptify(Document::Lists, SQLEvent::Select("list_timeline")),
+        load_script("select_list_timeline"),
+    );
+
+    // Scheduled tweets script
+    scripts.insert(
+        scriptify(Document::ScheduledTweets, SQLEvent::Insert),
+        load_script("insert_scheduled_tweet"),
+    );
+    scripts.insert(
+        scriptify(Document::ScheduledTweets, SQLEvent::Delete),
+        load_script("delete_scheduled_tweet"),
+    );
+    // Scheduled tweets being published are removed like cancelled ones, their rows stay locked
+    // until then
+    scripts.insert(
+        scriptify(Document::ScheduledTweets, SQLEvent::DeleteBy("taken")),
+        load_script("delete_scheduled_tweet"),
+    );
+    scripts.insert(
+        scriptify(Document::ScheduledTweets, SQLEvent::Select("scheduled")),
+        load_script("select_scheduled_tweets"),
+    );
+    scripts.insert(
+        scriptify(Document::ScheduledTweets, SQLEvent::Select("due")),
+        load_script("select_due_scheduled_tweets"),
+    );
+
+    // Blocks script
+    scripts.insert(
+        scriptify(Document::Blocks, SQLEvent::Insert),
+        load_script("insert_block"),
+    );
+    scripts.insert(
+        scriptify(Document::Blocks, SQLEvent::Delete),
+        load_script("delete_block"),
+    );
+
+    // Mutes script
+    scripts.insert(
+        scriptify(Document::Mutes, SQLEvent::Insert),
+        load_script("insert_mute"),
+    );
+    scripts.insert(
+        scriptify(Document::Mutes, SQLEvent::Delete),
+        load_script("delete_mute"),
+    );
     scripts
 });
@@ -1,6 +1,7 @@
+use bb8::RunError;
 use redis::RedisError;
 use thiserror::Error;
-use tokio_postgres::Error as PostgresError;
+use tokio_postgres::{error::SqlState, Error as PostgresError};
 
 #[derive(Error, Debug)]
 pub enum DatabaseError {
@@ -31,6 +32,14 @@ pub enum DatabaseError {
     #[error("The key being inserted already exists")]
     TxKeyAlreadyExists,
 
+    /// No pooled connection became available before the checkout timeout
+    #[error("Timed out waiting for a pooled connection")]
+    PoolTimedOut,
+
+    /// Migrations on disk don't match the ones applied to the database
+    #[error("Migration error: {0}")]
+    Migration(String),
+
     #[error("Database instance is not initialized")]
     DbNotInitialized,
 
@@ -46,6 +55,21 @@ pub enum DatabaseError {
 
 impl From<PostgresError> for DatabaseError {
     fn from(err: PostgresError) -> DatabaseError {
+        if err.code() == Some(&SqlState::UNIQUE_VIOLATION) {
+            return DatabaseError::TxKeyAlreadyExists;
+        }
+        // Referenced row, e.g. the liked tweet, does not exist
+        if err.code() == Some(&SqlState::FOREIGN_KEY_VIOLATION) {
+            return DatabaseError::TxnKeyNotFound;
+        }
+        // Row breaks a table constraint, e.g. a user messaging themselves or following a blocked user
+        if err.code() == Some(&SqlState::CHECK_VIOLATION) {
+            return DatabaseError::TxConditionNotMet;
+        }
+        // Value longer than its column allows, e.g. a handle over 15 characters
+        if err.code() == Some(&SqlState::STRING_DATA_RIGHT_TRUNCATION) {
+            return DatabaseError::TxConditionNotMet;
+        }
         DatabaseError::Database(err.to_string())
     }
 }
@@ -55,3 +79,15 @@ impl From<RedisError> for DatabaseError {
         DatabaseError::Database(err.to_string())
     }
 }
+
+impl<E> From<RunError<E>> for DatabaseError
+where
+    E: Into<DatabaseError>,
+{
+    fn from(err: RunError<E>) -> DatabaseError {
+        match err {
+            RunError::User(e) => e.into(),
+            RunError::TimedOut => DatabaseError::PoolTimedOut,
+        }
+    }
+}
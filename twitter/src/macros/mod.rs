@@ -3,6 +3,3 @@ mod db;
 
 #[macro_use]
 mod tx;
-
-pub use db::*;
-pub use tx::*;
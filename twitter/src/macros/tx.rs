@@ -82,6 +82,43 @@ macro_rules! impl_global_transaction {
 					}
 				}
 
+				async fn bulk_set<K, A>(&mut self, keys: K, args: Vec<A>) -> Result<(), DatabaseError>
+				where
+					K: Into<Key> + Send,
+					A: Into<Arg> + Send
+				{
+					match self {
+						$(
+							#[cfg(feature = $feat)]
+							Transaction {
+								inner: Inner::$x(ds),
+								..
+							} => ds.bulk_set(keys, args).await,
+						)*
+					}
+				}
+
+				async fn del<K, A>(
+					&mut self,
+					key: K,
+					val: A,
+					keywords: KeywordBucket
+				) -> Result<(), DatabaseError>
+				where
+					K: Into<Key> + Send,
+					A: Into<Arg> + Send
+				{
+					match self {
+						$(
+							#[cfg(feature = $feat)]
+							Transaction {
+								inner: Inner::$x(ds),
+								..
+							} => ds.del(key, val, keywords).await,
+						)*
+					}
+				}
+
 				async fn get<K, A, V>(
 					&self, key: K,
 					args: A,
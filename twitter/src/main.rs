@@ -1,4 +1,10 @@
-use crate::{models::Follow, structures::SimpleTransaction, utils::load_from_csv};
+use std::time::Duration;
+
+use crate::{
+    models::Follow,
+    structures::{PoolConfig, SimpleTransaction},
+    utils::{load_from_csv, sss, tokenize},
+};
 use api::TwitterApi;
 use colored::Colorize;
 use conn::DATABASE_CONNECTIONS;
@@ -7,6 +13,8 @@ use indicatif::ProgressBar;
 use models::Tweet;
 use rand::seq::SliceRandom;
 use storage::{Database, DatabaseRef, DatabaseVariant};
+#[cfg(feature = "rdb_postgres")]
+use storage::{Migrator, PostgresAdapter};
 use utils::{start_benchmarking, stop_benchmarking};
 
 mod api;
@@ -19,12 +27,21 @@ mod macros;
 mod misc;
 mod models;
 mod repo;
+mod scheduler;
 mod storage;
 mod utils;
 
 /* Use test sample CSV or real data CSV */
 static GLOBAL_USE_SAMPLE: bool = false;
 static GLOBAL_WRITE: bool = true;
+/* Number of tweets posted per batch insert (e.g. 1, 5, 50 or 500) */
+static GLOBAL_BATCH_SIZE: usize = 5;
+/* Number of recent tweets copied into a new follower home timeline */
+pub static GLOBAL_BACKFILL_SIZE: usize = 10;
+/* Number of entries per page of cursor paginated reads, e.g. bookmarks */
+pub static GLOBAL_PAGE_SIZE: i64 = 20;
+/* Interval at which the background scheduler publishes due scheduled tweets */
+static GLOBAL_SCHEDULER_PERIOD: Duration = Duration::from_secs(1);
 /* Set the strategy number used for testing Redis */
 pub static REDIS_STRATEGY: i32 = 2;
 
@@ -42,7 +59,7 @@ fn benchmark_load_tweets_from_csv() -> Vec<Tweet> {
         let user_id = record.get(0).unwrap();
         let parsed_user_id = user_id.parse::<i32>().unwrap();
         let tweet_text = record.get(1).unwrap().to_string();
-        let tweet = Tweet::partial_new(parsed_user_id.clone(), tweet_text);
+        let tweet = Tweet::partial_new(parsed_user_id, tweet_text);
         loaded_tweets.push(tweet);
     }
     stop_benchmarking(t);
@@ -51,7 +68,7 @@ fn benchmark_load_tweets_from_csv() -> Vec<Tweet> {
 }
 
 async fn benchmark_load_follows_from_csv(
-    twitter_api: &mut TwitterApi,
+    twitter_api: &TwitterApi,
     save: bool,
 ) -> Result<Vec<i32>, DatabaseError> {
     let mut followers = Vec::<i32>::default();
@@ -78,7 +95,7 @@ async fn benchmark_load_follows_from_csv(
 
         let follow_id = record.get(1).unwrap();
         let parsed_follow_id = follow_id.parse::<i32>().unwrap();
-        let follow = Follow::partial_new(parsed_user_id.clone(), parsed_follow_id.clone());
+        let follow = Follow::partial_new(parsed_user_id, parsed_follow_id);
         follows.push(follow);
     }
 
@@ -87,15 +104,26 @@ async fn benchmark_load_follows_from_csv(
     Ok(followers)
 }
 
+#[allow(dead_code)]
+async fn benchmark_bulk_load_tweets(
+    twitter_api: &TwitterApi,
+    loaded_tweets: Vec<Tweet>,
+) -> Result<(), DatabaseError> {
+    let t = start_benchmarking("PREPARATION", "Bulk load tweets");
+    twitter_api.bulk_load_tweets(loaded_tweets).await?;
+    stop_benchmarking(t);
+    Ok(())
+}
+
 #[allow(dead_code)]
 async fn benchmark_post_tweets_single_insert(
-    twitter_api: &mut TwitterApi,
+    twitter_api: &TwitterApi,
     loaded_tweets: Vec<Tweet>,
 ) -> Result<(), DatabaseError> {
     let t = start_benchmarking("POST TWEETS", "Using single insert");
     let pb = ProgressBar::new(loaded_tweets.len().try_into().unwrap());
     let tx = &mut twitter_api.repo.mut_tx().await;
-    for tweet in loaded_tweets.to_vec() {
+    for tweet in loaded_tweets.iter().cloned() {
         pb.inc(1);
         twitter_api.post_tweet(tweet, tx).await?;
     }
@@ -113,27 +141,19 @@ async fn benchmark_post_tweets_single_insert(
 }
 
 async fn benchmark_post_tweets_batch_insert(
-    twitter_api: &mut TwitterApi,
+    twitter_api: &TwitterApi,
     loaded_tweets: Vec<Tweet>,
+    batch_size: usize,
 ) -> Result<(), DatabaseError> {
-    let t = start_benchmarking("POST TWEETS", "Batch insert | Batch size = 5");
-    let batch_size: usize = 5;
-    let mut cur = 0;
+    let t = start_benchmarking(
+        "POST TWEETS",
+        sss(format!("Batch insert | Batch size = {}", batch_size)),
+    );
     let pb = ProgressBar::new(loaded_tweets.len().try_into().unwrap());
     let tx = &mut twitter_api.repo.mut_tx().await;
-    while cur <= loaded_tweets.len() {
-        pb.inc(batch_size.try_into().unwrap());
-        let end = std::cmp::min(cur + batch_size, loaded_tweets.len());
-        let batch = &loaded_tweets.as_slice()[cur..end];
-
-        cur = cur + batch_size;
-        if batch.len() < batch_size {
-            for tweet in batch.to_vec() {
-                twitter_api.post_tweet(tweet, tx).await?;
-            }
-        } else {
-            twitter_api.batch_post_tweets(batch.to_vec(), tx).await?;
-        }
+    for batch in loaded_tweets.chunks(batch_size) {
+        pb.inc(batch.len().try_into().unwrap());
+        twitter_api.batch_post_tweets(batch.to_vec(), tx).await?;
     }
     tx.commit().await?;
     let requests = loaded_tweets.len() as u64;
@@ -149,17 +169,102 @@ async fn benchmark_post_tweets_batch_insert(
     Ok(())
 }
 
+// Third program: repeatedly search a random term picked from the loaded tweets
+async fn benchmark_search(
+    twitter_api: &TwitterApi,
+    loaded_tweets: &[Tweet],
+) -> Result<(), DatabaseError> {
+    let terms = loaded_tweets
+        .iter()
+        .take(10_000)
+        .flat_map(|tweet| tokenize(&tweet.tweet_text))
+        .collect::<Vec<String>>();
+    if terms.is_empty() {
+        return Ok(());
+    }
+    let tx = twitter_api.repo.tx().await;
+    let mut total_searches = 0;
+    let t = start_benchmarking("SEARCH", "Return the first page of a random term search");
+    while t.elapsed().as_secs() < 60 {
+        let term = terms.choose(&mut rand::thread_rng()).unwrap();
+        // Search as the author of a random loaded tweet
+        let viewer = loaded_tweets
+            .choose(&mut rand::thread_rng())
+            .unwrap()
+            .author();
+        twitter_api
+            .search(term.to_string(), 10, None, viewer, &tx)
+            .await?;
+        total_searches += 1;
+    }
+    println!("Total searches: {}", total_searches);
+    let sps = total_searches / t.elapsed().as_secs();
+    println!("Search per second: {}", sps);
+    stop_benchmarking(t);
+
+    Ok(())
+}
+
 fn get_connection_str(variant: DatabaseVariant) -> &'static str {
     DATABASE_CONNECTIONS.get(&variant).unwrap()
 }
 
+#[cfg(feature = "rdb_postgres")]
+fn parse_version(version: Option<&String>) -> Result<Option<i32>, DatabaseError> {
+    version
+        .map(|v| {
+            v.parse::<i32>()
+                .map_err(|_| DatabaseError::Migration(format!("invalid version {}", v)))
+        })
+        .transpose()
+}
+
+// Usage: cargo run -- migrate [up [version] | down [version] | status]
+#[cfg(feature = "rdb_postgres")]
+async fn run_migrations(command: &[String]) -> Result<(), DatabaseError> {
+    let conn = get_connection_str(DatabaseVariant::Postgres);
+    let pool = PostgresAdapter::build_pool(conn, &PoolConfig::default()).await?;
+    let migrator = Migrator::new(pool);
+    match command.first().map(String::as_str) {
+        Some("up") | None => migrator.up(parse_version(command.get(1))?).await?,
+        Some("down") => migrator.down(parse_version(command.get(1))?).await?,
+        Some("status") => {
+            let applied = migrator.applied().await?;
+            for migration in migrator.migrations() {
+                let status = if applied.iter().any(|a| a.version == migration.version) {
+                    "applied".green()
+                } else {
+                    "pending".yellow()
+                };
+                println!("{:04} {} : {}", migration.version, migration.name, status);
+            }
+        }
+        Some(other) => {
+            return Err(DatabaseError::Migration(format!(
+                "unknown command {}",
+                other
+            )))
+        }
+    }
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> Result<(), DatabaseError> {
+    #[cfg(feature = "rdb_postgres")]
+    {
+        let args = std::env::args().collect::<Vec<String>>();
+        if args.get(1).map(String::as_str) == Some("migrate") {
+            return run_migrations(&args[2..]).await;
+        }
+    }
+
     let variant = DatabaseVariant::Redis;
     let conn = get_connection_str(variant.clone());
-    let database = Database::connect(variant, conn, GLOBAL_WRITE).await;
+    let database = Database::connect(variant, conn, GLOBAL_WRITE, PoolConfig::default()).await;
     let database_ref = DatabaseRef::new(database);
-    let mut twitter_api = TwitterApi::new(database_ref);
+    let twitter_api = TwitterApi::new(database_ref);
+    scheduler::spawn(twitter_api.clone(), GLOBAL_SCHEDULER_PERIOD);
 
     // First program:
     // Write one program that reads pre-generated tweets from the file tweets.csv. Note that the
@@ -170,10 +275,12 @@ async fn main() -> Result<(), DatabaseError> {
     // per second. Can MySQL keep up?)  Insert tweets as you read them from the file. Batch no
     // more than 5 tweets at a time into the insert.
     let loaded_tweets = benchmark_load_tweets_from_csv();
-    let followers = benchmark_load_follows_from_csv(&mut twitter_api, GLOBAL_WRITE).await?;
+    let followers = benchmark_load_follows_from_csv(&twitter_api, GLOBAL_WRITE).await?;
     if GLOBAL_WRITE {
-        // benchmark_post_tweets_single_insert(&mut twitter_api, loaded_tweets.to_vec()).await?;
-        benchmark_post_tweets_batch_insert(&mut twitter_api, loaded_tweets.to_vec()).await?;
+        // benchmark_bulk_load_tweets(&twitter_api, loaded_tweets.to_vec()).await?;
+        // benchmark_post_tweets_single_insert(&twitter_api, loaded_tweets.to_vec()).await?;
+        benchmark_post_tweets_batch_insert(&twitter_api, loaded_tweets.to_vec(), GLOBAL_BATCH_SIZE)
+            .await?;
     }
 
     // Second Program:
@@ -189,7 +296,7 @@ async fn main() -> Result<(), DatabaseError> {
     let t = start_benchmarking("USER TIMELINE", "Return that random user’s home timeline");
     while t.elapsed().as_secs() < 120 {
         // Repeatedly select random user from list of followers
-        let user_id = followers.choose(&mut rand::thread_rng()).unwrap().clone();
+        let user_id = *followers.choose(&mut rand::thread_rng()).unwrap();
         twitter_api.get_timeline(user_id, &tx).await?;
         // println!("tweets: {:?}", tweets); // Uncomment this line to view the fetched tweets
         total_timelines_fetched += 1;
@@ -199,5 +306,7 @@ async fn main() -> Result<(), DatabaseError> {
     println!("Timeline per second: {}", tps);
     stop_benchmarking(t);
 
+    benchmark_search(&twitter_api, &loaded_tweets).await?;
+
     Ok(())
 }
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS Tweets;
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS Follows;
\ No newline at end of file
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS tweet_user_id_index;
+DROP INDEX IF EXISTS follow_to_id_index;
+DROP INDEX IF EXISTS follow_from_id_index;
@@ -0,0 +1,8 @@
+CREATE INDEX IF NOT EXISTS tweet_user_id_index
+ON Tweets (user_id);
+
+CREATE INDEX IF NOT EXISTS follow_to_id_index
+ON Follows (to_id);
+
+CREATE INDEX IF NOT EXISTS follow_from_id_index
+ON Follows (from_id);
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS Users (
+	user_id INT NOT NULL,
+	handle VARCHAR(15) NOT NULL,
+	display_name VARCHAR(50) NOT NULL,
+	bio VARCHAR(160) NOT NULL DEFAULT '',
+	created_ts TIMESTAMP DEFAULT current_timestamp,
+	PRIMARY KEY(user_id)
+);
+
+-- Handles are unique regardless of their case
+CREATE UNIQUE INDEX IF NOT EXISTS user_handle_unique_index
+ON Users (LOWER(handle));
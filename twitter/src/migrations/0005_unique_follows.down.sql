@@ -0,0 +1 @@
+DROP INDEX IF EXISTS follow_unique_index;
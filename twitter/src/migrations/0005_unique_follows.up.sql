@@ -0,0 +1,9 @@
+-- Remove duplicate follows, keeping the earliest one
+DELETE FROM Follows a
+USING Follows b
+WHERE a.from_id = b.from_id
+	AND a.to_id = b.to_id
+	AND a.follow_id > b.follow_id;
+
+CREATE UNIQUE INDEX IF NOT EXISTS follow_unique_index
+ON Follows (from_id, to_id);
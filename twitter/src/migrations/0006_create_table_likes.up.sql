@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS Likes (
+	user_id INT NOT NULL,
+	tweet_id INT NOT NULL REFERENCES Tweets(tweet_id) ON DELETE CASCADE,
+	like_ts TIMESTAMP DEFAULT current_timestamp,
+	-- A user likes a tweet at most once
+	PRIMARY KEY(user_id, tweet_id)
+);
+
+CREATE INDEX IF NOT EXISTS like_tweet_id_index
+ON Likes (tweet_id);
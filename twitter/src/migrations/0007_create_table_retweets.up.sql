@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS Retweets (
+	user_id INT NOT NULL,
+	tweet_id INT NOT NULL REFERENCES Tweets(tweet_id) ON DELETE CASCADE,
+	retweet_ts TIMESTAMP DEFAULT current_timestamp,
+	-- A user retweets a tweet at most once
+	PRIMARY KEY(user_id, tweet_id)
+);
+
+CREATE INDEX IF NOT EXISTS retweet_tweet_id_index
+ON Retweets (tweet_id);
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS tweet_in_reply_to_index;
+ALTER TABLE Tweets
+DROP COLUMN IF EXISTS conversation_id,
+DROP COLUMN IF EXISTS in_reply_to;
@@ -0,0 +1,7 @@
+ALTER TABLE Tweets
+ADD COLUMN IF NOT EXISTS in_reply_to INT REFERENCES Tweets(tweet_id) ON DELETE SET NULL,
+-- Root tweet of the thread, NULL for tweets that are not replies
+ADD COLUMN IF NOT EXISTS conversation_id INT;
+
+CREATE INDEX IF NOT EXISTS tweet_in_reply_to_index
+ON Tweets (in_reply_to);
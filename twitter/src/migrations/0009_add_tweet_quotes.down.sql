@@ -0,0 +1,2 @@
+ALTER TABLE Tweets
+DROP COLUMN IF EXISTS quoted_tweet_id;
@@ -0,0 +1,3 @@
+-- No foreign key, the quote keeps pointing to the original after it is deleted
+ALTER TABLE Tweets
+ADD COLUMN IF NOT EXISTS quoted_tweet_id INT;
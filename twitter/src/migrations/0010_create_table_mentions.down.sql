@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS tweet_mentions_trigger ON Tweets;
+DROP FUNCTION IF EXISTS insert_tweet_mentions;
+DROP TABLE IF EXISTS Mentions;
@@ -0,0 +1,26 @@
+CREATE TABLE IF NOT EXISTS Mentions (
+	user_id INT NOT NULL,
+	tweet_id INT NOT NULL REFERENCES Tweets(tweet_id) ON DELETE CASCADE,
+	PRIMARY KEY(user_id, tweet_id)
+);
+
+-- Store an edge for every @handle of the tweet text matching a user, whichever way
+-- the tweet was inserted (single insert, batch insert or COPY)
+CREATE OR REPLACE FUNCTION insert_tweet_mentions() RETURNS TRIGGER AS $$
+BEGIN
+	INSERT INTO Mentions (user_id, tweet_id)
+	SELECT DISTINCT u.user_id, NEW.tweet_id
+	FROM regexp_matches(
+		NEW.tweet_text,
+		'(?:^|[^A-Za-z0-9_])@([A-Za-z0-9_]{1,15})(?![A-Za-z0-9_])',
+		'g'
+	) AS m
+	INNER JOIN Users AS u
+	ON LOWER(u.handle) = LOWER(m[1]);
+	RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE TRIGGER tweet_mentions_trigger
+AFTER INSERT ON Tweets
+FOR EACH ROW EXECUTE FUNCTION insert_tweet_mentions();
@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS tweet_hashtags_trigger ON Tweets;
+DROP FUNCTION IF EXISTS insert_tweet_hashtags;
+DROP TABLE IF EXISTS Hashtags;
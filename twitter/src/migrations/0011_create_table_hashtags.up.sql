@@ -0,0 +1,25 @@
+CREATE TABLE IF NOT EXISTS Hashtags (
+	tag VARCHAR(140) NOT NULL,
+	tweet_id INT NOT NULL REFERENCES Tweets(tweet_id) ON DELETE CASCADE,
+	-- Copied from the tweet so trending hashtags are aggregated without a join
+	tweet_ts TIMESTAMP NOT NULL,
+	PRIMARY KEY(tag, tweet_id)
+);
+
+CREATE INDEX IF NOT EXISTS hashtag_tweet_ts_index
+ON Hashtags (tweet_ts);
+
+-- Store the lowercased #hashtags of the tweet text, whichever way the tweet was inserted
+CREATE OR REPLACE FUNCTION insert_tweet_hashtags() RETURNS TRIGGER AS $$
+BEGIN
+	INSERT INTO Hashtags (tag, tweet_id, tweet_ts)
+	SELECT DISTINCT LOWER(m[1]), NEW.tweet_id, NEW.tweet_ts
+	FROM regexp_matches(NEW.tweet_text, '(?:^|[^A-Za-z0-9_])#([A-Za-z0-9_]+)', 'g') AS m
+	WHERE m[1] ~ '[A-Za-z]';
+	RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE TRIGGER tweet_hashtags_trigger
+AFTER INSERT ON Tweets
+FOR EACH ROW EXECUTE FUNCTION insert_tweet_hashtags();
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS tweet_search_vector_index;
+ALTER TABLE Tweets
+DROP COLUMN IF EXISTS search_vector;
@@ -0,0 +1,3 @@
+-- Supports LIKE 'prefix%' lookups regardless of the database collation
+CREATE INDEX IF NOT EXISTS user_handle_prefix_index
+ON Users (LOWER(handle) text_pattern_ops);
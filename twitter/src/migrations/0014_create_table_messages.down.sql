@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS Messages;
+DROP TABLE IF EXISTS Conversations;
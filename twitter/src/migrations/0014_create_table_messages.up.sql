@@ -0,0 +1,22 @@
+CREATE TABLE IF NOT EXISTS Conversations (
+	conversation_id SERIAL PRIMARY KEY,
+	-- Participants are stored in id order so a pair has a single conversation
+	user_a INT NOT NULL,
+	user_b INT NOT NULL,
+	CHECK (user_a < user_b),
+	UNIQUE (user_a, user_b)
+);
+
+CREATE INDEX IF NOT EXISTS conversation_user_b_index
+ON Conversations (user_b);
+
+CREATE TABLE IF NOT EXISTS Messages (
+	message_id SERIAL PRIMARY KEY,
+	conversation_id INT NOT NULL REFERENCES Conversations(conversation_id) ON DELETE CASCADE,
+	sender_id INT NOT NULL,
+	message_text TEXT NOT NULL,
+	message_ts TIMESTAMP DEFAULT current_timestamp
+);
+
+CREATE INDEX IF NOT EXISTS message_conversation_id_index
+ON Messages (conversation_id, message_id);
@@ -0,0 +1,7 @@
+DROP TRIGGER IF EXISTS conversation_block_trigger ON Conversations;
+DROP FUNCTION IF EXISTS reject_blocked_conversation;
+DROP TRIGGER IF EXISTS follow_block_trigger ON Follows;
+DROP FUNCTION IF EXISTS reject_blocked_follow;
+DROP FUNCTION IF EXISTS is_blocked;
+DROP TABLE IF EXISTS Mutes;
+DROP TABLE IF EXISTS Blocks;
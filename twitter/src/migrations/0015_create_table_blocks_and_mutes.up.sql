@@ -0,0 +1,58 @@
+CREATE TABLE IF NOT EXISTS Blocks (
+	user_id INT NOT NULL,
+	blocked_id INT NOT NULL,
+	block_ts TIMESTAMP DEFAULT current_timestamp,
+	CHECK (user_id <> blocked_id),
+	PRIMARY KEY(user_id, blocked_id)
+);
+
+CREATE INDEX IF NOT EXISTS block_blocked_id_index
+ON Blocks (blocked_id);
+
+CREATE TABLE IF NOT EXISTS Mutes (
+	user_id INT NOT NULL,
+	muted_id INT NOT NULL,
+	mute_ts TIMESTAMP DEFAULT current_timestamp,
+	CHECK (user_id <> muted_id),
+	PRIMARY KEY(user_id, muted_id)
+);
+
+-- Whether either user blocks the other
+CREATE OR REPLACE FUNCTION is_blocked(a INT, b INT) RETURNS BOOLEAN AS $$
+	SELECT EXISTS (
+		SELECT 1
+		FROM Blocks
+		WHERE (user_id = a AND blocked_id = b)
+		OR (user_id = b AND blocked_id = a)
+	);
+$$ LANGUAGE sql STABLE;
+
+-- Blocked users can't follow each other, whichever way the follow is inserted
+CREATE OR REPLACE FUNCTION reject_blocked_follow() RETURNS TRIGGER AS $$
+BEGIN
+	IF is_blocked(NEW.from_id, NEW.to_id) THEN
+		RAISE EXCEPTION 'user % and user % block each other', NEW.from_id, NEW.to_id
+		USING ERRCODE = 'check_violation';
+	END IF;
+	RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE TRIGGER follow_block_trigger
+BEFORE INSERT ON Follows
+FOR EACH ROW EXECUTE FUNCTION reject_blocked_follow();
+
+-- Every message upserts its conversation, so blocked users can't message each other
+CREATE OR REPLACE FUNCTION reject_blocked_conversation() RETURNS TRIGGER AS $$
+BEGIN
+	IF is_blocked(NEW.user_a, NEW.user_b) THEN
+		RAISE EXCEPTION 'user % and user % block each other', NEW.user_a, NEW.user_b
+		USING ERRCODE = 'check_violation';
+	END IF;
+	RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE TRIGGER conversation_block_trigger
+BEFORE INSERT ON Conversations
+FOR EACH ROW EXECUTE FUNCTION reject_blocked_conversation();
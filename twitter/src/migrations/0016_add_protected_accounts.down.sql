@@ -0,0 +1,9 @@
+DROP TRIGGER IF EXISTS retweet_protected_trigger ON Retweets;
+DROP FUNCTION IF EXISTS reject_protected_retweet;
+DROP TRIGGER IF EXISTS follow_protected_trigger ON Follows;
+DROP FUNCTION IF EXISTS request_protected_follow;
+DROP INDEX IF EXISTS follow_pending_index;
+ALTER TABLE Follows
+DROP COLUMN IF EXISTS status;
+ALTER TABLE Users
+DROP COLUMN IF EXISTS protected;
@@ -0,0 +1,46 @@
+ALTER TABLE Users
+ADD COLUMN IF NOT EXISTS protected BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE Follows
+-- Follows of protected accounts are pending until the owner approves them
+ADD COLUMN IF NOT EXISTS status VARCHAR(8) NOT NULL DEFAULT 'approved'
+CHECK (status IN ('pending', 'approved'));
+
+CREATE INDEX IF NOT EXISTS follow_pending_index
+ON Follows (to_id) WHERE status = 'pending';
+
+-- Follows of protected accounts start as requests, whichever way they are inserted
+CREATE OR REPLACE FUNCTION request_protected_follow() RETURNS TRIGGER AS $$
+BEGIN
+	IF EXISTS (SELECT 1 FROM Users WHERE user_id = NEW.to_id AND protected) THEN
+		NEW.status := 'pending';
+	END IF;
+	RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE TRIGGER follow_protected_trigger
+BEFORE INSERT ON Follows
+FOR EACH ROW EXECUTE FUNCTION request_protected_follow();
+
+-- Protected tweets would otherwise reach the retweeter's followers
+CREATE OR REPLACE FUNCTION reject_protected_retweet() RETURNS TRIGGER AS $$
+BEGIN
+	IF EXISTS (
+		SELECT 1
+		FROM Tweets AS t
+		INNER JOIN Users AS u
+		ON u.user_id = t.user_id
+		WHERE t.tweet_id = NEW.tweet_id
+		AND u.protected
+	) THEN
+		RAISE EXCEPTION 'tweet % is protected', NEW.tweet_id
+		USING ERRCODE = 'check_violation';
+	END IF;
+	RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE TRIGGER retweet_protected_trigger
+BEFORE INSERT ON Retweets
+FOR EACH ROW EXECUTE FUNCTION reject_protected_retweet();
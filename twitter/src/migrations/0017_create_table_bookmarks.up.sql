@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS Bookmarks (
+	user_id INT NOT NULL,
+	-- Bookmarks of deleted tweets are dropped with them
+	tweet_id INT NOT NULL REFERENCES Tweets(tweet_id) ON DELETE CASCADE,
+	bookmark_ts TIMESTAMP DEFAULT current_timestamp,
+	PRIMARY KEY(user_id, tweet_id)
+);
+
+CREATE INDEX IF NOT EXISTS bookmark_user_ts_index
+ON Bookmarks (user_id, bookmark_ts);
+
+CREATE INDEX IF NOT EXISTS bookmark_tweet_id_index
+ON Bookmarks (tweet_id);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS ListMembers;
+DROP TABLE IF EXISTS Lists;
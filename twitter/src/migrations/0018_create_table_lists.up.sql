@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS Lists (
+	list_id SERIAL PRIMARY KEY,
+	owner_id INT NOT NULL,
+	list_name VARCHAR(25) NOT NULL,
+	created_ts TIMESTAMP DEFAULT current_timestamp,
+	-- Lists are addressed by their owner and name
+	UNIQUE (owner_id, list_name)
+);
+
+CREATE TABLE IF NOT EXISTS ListMembers (
+	list_id INT NOT NULL REFERENCES Lists(list_id) ON DELETE CASCADE,
+	user_id INT NOT NULL,
+	added_ts TIMESTAMP DEFAULT current_timestamp,
+	PRIMARY KEY(list_id, user_id)
+);
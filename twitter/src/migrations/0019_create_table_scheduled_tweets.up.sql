@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS ScheduledTweets (
+	scheduled_id SERIAL PRIMARY KEY,
+	user_id INT NOT NULL,
+	tweet_text VARCHAR(255) NOT NULL,
+	-- Moved into Tweets by the scheduler once reached
+	publish_at TIMESTAMP NOT NULL,
+	scheduled_ts TIMESTAMP DEFAULT current_timestamp
+);
+
+CREATE INDEX IF NOT EXISTS scheduled_publish_at_index
+ON ScheduledTweets (publish_at);
+
+CREATE INDEX IF NOT EXISTS scheduled_user_publish_at_index
+ON ScheduledTweets (user_id, publish_at);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS tweet_orphan_reply_index;
+DROP TRIGGER IF EXISTS tweet_reparent_trigger ON Tweets;
+DROP FUNCTION IF EXISTS reparent_replies;
@@ -0,0 +1,18 @@
+-- Replies of a deleted tweet move up to its parent so the rest of the thread stays reachable.
+-- Replies of a deleted conversation root become top level replies of the conversation
+CREATE OR REPLACE FUNCTION reparent_replies() RETURNS TRIGGER AS $$
+BEGIN
+	UPDATE Tweets
+	SET in_reply_to = OLD.in_reply_to
+	WHERE in_reply_to = OLD.tweet_id;
+	RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE TRIGGER tweet_reparent_trigger
+BEFORE DELETE ON Tweets
+FOR EACH ROW EXECUTE FUNCTION reparent_replies();
+
+-- Top level replies left by a deleted conversation root
+CREATE INDEX IF NOT EXISTS tweet_orphan_reply_index
+ON Tweets (conversation_id) WHERE in_reply_to IS NULL AND conversation_id IS NOT NULL;
@@ -0,0 +1,10 @@
+-- Index the words as written, without stemming or stopwords, like the Redis inverted index
+DROP INDEX IF EXISTS tweet_search_vector_index;
+ALTER TABLE Tweets
+DROP COLUMN IF EXISTS search_vector;
+ALTER TABLE Tweets
+ADD COLUMN IF NOT EXISTS search_vector TSVECTOR
+GENERATED ALWAYS AS (to_tsvector('simple', tweet_text)) STORED;
+
+CREATE INDEX IF NOT EXISTS tweet_search_vector_index
+ON Tweets USING GIN (search_vector);
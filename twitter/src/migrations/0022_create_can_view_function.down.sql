@@ -0,0 +1 @@
+DROP FUNCTION IF EXISTS can_view;
@@ -0,0 +1,13 @@
+-- Whether the viewer can see the author's tweets, protected accounts only show them to
+-- themselves and their approved followers
+CREATE OR REPLACE FUNCTION can_view(viewer INT, author INT) RETURNS BOOLEAN AS $$
+	SELECT viewer = author
+	OR NOT EXISTS (SELECT 1 FROM Users WHERE user_id = author AND protected)
+	OR EXISTS (
+		SELECT 1
+		FROM Follows
+		WHERE from_id = viewer
+		AND to_id = author
+		AND status = 'approved'
+	);
+$$ LANGUAGE sql STABLE;
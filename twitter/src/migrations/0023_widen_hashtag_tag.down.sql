@@ -0,0 +1,4 @@
+-- Hashtags longer than the previous limit no longer fit
+DELETE FROM Hashtags WHERE LENGTH(tag) > 140;
+ALTER TABLE Hashtags
+ALTER COLUMN tag TYPE VARCHAR(140);
@@ -0,0 +1,4 @@
+-- Hashtags can be as long as the tweet text, the trigger would otherwise fail the whole insert,
+-- batch or bulk load of a tweet with a longer one
+ALTER TABLE Hashtags
+ALTER COLUMN tag TYPE VARCHAR(255);
@@ -1,14 +1,36 @@
-use std::time::SystemTime;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crate::{misc::Identifier, structures::FromPostgresRow};
+use crate::{
+    misc::Identifier,
+    structures::{FromPostgresRow, FromRedisValue},
+};
 use serde::{Deserialize, Serialize};
 
+/// Follows of protected accounts are pending until the owner approves them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FollowStatus {
+    Pending,
+    #[default]
+    Approved,
+}
+
+impl From<&str> for FollowStatus {
+    fn from(status: &str) -> Self {
+        match status {
+            "pending" => FollowStatus::Pending,
+            _ => FollowStatus::Approved,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Follow {
     id: Identifier,
     user_id: Identifier,
     follows_id: Identifier,
     follows_ts: SystemTime,
+    pub status: FollowStatus,
 }
 
 impl Default for Follow {
@@ -18,6 +40,7 @@ impl Default for Follow {
             follows_id: Default::default(),
             user_id: Default::default(),
             id: Default::default(),
+            status: Default::default(),
         }
     }
 }
@@ -29,6 +52,23 @@ impl FromPostgresRow for Follow {
             user_id: r.get(1),
             follows_id: r.get(2),
             follows_ts: r.get(3),
+            status: FollowStatus::from(r.get::<_, &str>(4)),
+        }
+    }
+}
+
+impl FromRedisValue for Follow {
+    /// Follow requests are read from Redis as a JSON object of the follow fields
+    fn from_redis_value(v: String) -> Self {
+        let fields: HashMap<String, String> = serde_json::from_str(&v).unwrap();
+        let field = |name: &str| fields.get(name).cloned().unwrap_or_default();
+        Follow {
+            id: Default::default(),
+            user_id: field("from_id").parse().unwrap_or_default(),
+            follows_id: field("to_id").parse().unwrap_or_default(),
+            follows_ts: UNIX_EPOCH
+                + Duration::from_secs(field("follow_ts").parse().unwrap_or_default()),
+            status: FollowStatus::from(field("status").as_str()),
         }
     }
 }
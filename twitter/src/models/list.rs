@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::misc::{Identifier, UnixTimestamp};
+use crate::structures::{FromPostgresRow, FromRedisValue};
+use serde::{Deserialize, Serialize};
+
+/// Named list of accounts curated by its owner, addressed by the owner and its name
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct List {
+    pub owner_id: Identifier,
+    pub list_name: String,
+    pub member_count: i64,
+    pub created_ts: UnixTimestamp,
+}
+
+impl FromRedisValue for List {
+    /// Lists are read from Redis as a JSON object of the list fields
+    fn from_redis_value(v: String) -> Self {
+        let fields: HashMap<String, String> = serde_json::from_str(&v).unwrap();
+        let field = |name: &str| fields.get(name).cloned().unwrap_or_default();
+        List {
+            owner_id: field("owner_id").parse().unwrap_or_default(),
+            list_name: field("list_name"),
+            member_count: field("member_count").parse().unwrap_or_default(),
+            created_ts: UNIX_EPOCH
+                + Duration::from_secs(field("created_ts").parse().unwrap_or_default()),
+        }
+    }
+}
+
+impl FromPostgresRow for List {
+    fn from_pg_row(r: tokio_postgres::Row) -> Self {
+        List {
+            owner_id: r.get(0),
+            list_name: r.get(1),
+            member_count: r.get(2),
+            created_ts: r.get(3),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::misc::{Identifier, UnixTimestamp};
+use crate::structures::{FromPostgresRow, FromRedisValue};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Message {
+    pub message_id: Identifier,
+    pub sender_id: Identifier,
+    pub recipient_id: Identifier,
+    pub message_text: String,
+    pub message_ts: UnixTimestamp,
+}
+
+impl FromRedisValue for Message {
+    /// Messages are read from Redis as a JSON object of the message fields
+    fn from_redis_value(v: String) -> Self {
+        let fields: HashMap<String, String> = serde_json::from_str(&v).unwrap();
+        let field = |name: &str| fields.get(name).cloned().unwrap_or_default();
+        Message {
+            message_id: field("message_id").parse().unwrap_or_default(),
+            sender_id: field("sender_id").parse().unwrap_or_default(),
+            recipient_id: field("recipient_id").parse().unwrap_or_default(),
+            message_text: field("message_text"),
+            message_ts: UNIX_EPOCH
+                + Duration::from_secs(field("message_ts").parse().unwrap_or_default()),
+        }
+    }
+}
+
+impl FromPostgresRow for Message {
+    fn from_pg_row(r: tokio_postgres::Row) -> Self {
+        Message {
+            message_id: r.get(0),
+            sender_id: r.get(1),
+            recipient_id: r.get(2),
+            message_text: r.get(3),
+            message_ts: r.get(4),
+        }
+    }
+}
+
+/// One-to-one conversation as seen by one of its participants
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Conversation {
+    /// The other participant
+    pub user_id: Identifier,
+    pub last_sender_id: Identifier,
+    pub last_message_text: String,
+    pub last_message_ts: UnixTimestamp,
+}
+
+impl FromRedisValue for Conversation {
+    /// Conversations are read from Redis as a JSON object of the peer and last message fields
+    fn from_redis_value(v: String) -> Self {
+        let fields: HashMap<String, String> = serde_json::from_str(&v).unwrap();
+        let field = |name: &str| fields.get(name).cloned().unwrap_or_default();
+        Conversation {
+            user_id: field("user_id").parse().unwrap_or_default(),
+            last_sender_id: field("last_sender_id").parse().unwrap_or_default(),
+            last_message_text: field("last_message_text"),
+            last_message_ts: UNIX_EPOCH
+                + Duration::from_secs(field("last_message_ts").parse().unwrap_or_default()),
+        }
+    }
+}
+
+impl FromPostgresRow for Conversation {
+    fn from_pg_row(r: tokio_postgres::Row) -> Self {
+        Conversation {
+            user_id: r.get(0),
+            last_sender_id: r.get(1),
+            last_message_text: r.get(2),
+            last_message_ts: r.get(3),
+        }
+    }
+}
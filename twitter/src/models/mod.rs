@@ -1,5 +1,15 @@
 mod follows;
+mod list;
+mod message;
+mod scheduled;
+mod trend;
 mod tweet;
+mod user;
 
 pub use follows::*;
+pub use list::*;
+pub use message::*;
+pub use scheduled::*;
+pub use trend::*;
 pub use tweet::*;
+pub use user::*;
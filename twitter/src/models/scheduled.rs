@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::misc::{Identifier, UnixTimestamp};
+use crate::structures::{FromPostgresRow, FromRedisValue};
+use serde::{Deserialize, Serialize};
+
+/// Tweet waiting for its publication time, it is posted as a regular tweet once reached
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScheduledTweet {
+    pub scheduled_id: Identifier,
+    pub user_id: Identifier,
+    pub tweet_text: String,
+    pub publish_at: UnixTimestamp,
+}
+
+impl FromRedisValue for ScheduledTweet {
+    /// Scheduled tweets are read from Redis as a JSON object of the scheduled tweet fields
+    fn from_redis_value(v: String) -> Self {
+        let fields: HashMap<String, String> = serde_json::from_str(&v).unwrap();
+        let field = |name: &str| fields.get(name).cloned().unwrap_or_default();
+        ScheduledTweet {
+            scheduled_id: field("scheduled_id").parse().unwrap_or_default(),
+            user_id: field("user_id").parse().unwrap_or_default(),
+            tweet_text: field("tweet_text"),
+            publish_at: UNIX_EPOCH
+                + Duration::from_secs(field("publish_at").parse().unwrap_or_default()),
+        }
+    }
+}
+
+impl FromPostgresRow for ScheduledTweet {
+    fn from_pg_row(r: tokio_postgres::Row) -> Self {
+        ScheduledTweet {
+            scheduled_id: r.get(0),
+            user_id: r.get(1),
+            tweet_text: r.get(2),
+            publish_at: r.get(3),
+        }
+    }
+}
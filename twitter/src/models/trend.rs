@@ -0,0 +1,29 @@
+use crate::structures::{FromPostgresRow, FromRedisValue};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Trend {
+    pub tag: String,
+    /// Number of tweets using the hashtag within the trending window
+    pub tweet_count: i64,
+}
+
+impl FromRedisValue for Trend {
+    /// Trends are read from Redis as `tag:count`
+    fn from_redis_value(v: String) -> Self {
+        let (tag, count) = v.rsplit_once(':').unwrap_or((&v, ""));
+        Trend {
+            tag: tag.to_string(),
+            tweet_count: count.parse().unwrap_or_default(),
+        }
+    }
+}
+
+impl FromPostgresRow for Trend {
+    fn from_pg_row(r: tokio_postgres::Row) -> Self {
+        Trend {
+            tag: r.get(0),
+            tweet_count: r.get(1),
+        }
+    }
+}
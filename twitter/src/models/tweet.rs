@@ -1,4 +1,7 @@
-use std::time::SystemTime;
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use crate::misc::{Identifier, UnixTimestamp};
 use crate::structures::{FromPostgresRow, FromRedisValue};
@@ -10,6 +13,17 @@ pub struct Tweet {
     user_id: Identifier,
     pub tweet_ts: UnixTimestamp,
     pub tweet_text: String,
+    /// Only populated by queries that count the likes, e.g. the home timeline
+    pub like_count: Option<i64>,
+    /// Set when the tweet shows up on a timeline as a retweet
+    pub retweeted_by: Option<Identifier>,
+    pub retweet_ts: Option<UnixTimestamp>,
+    pub in_reply_to: Option<Identifier>,
+    /// Root tweet of the thread, the tweet itself when it is not a reply
+    pub conversation_id: Option<Identifier>,
+    pub quoted_tweet_id: Option<Identifier>,
+    /// Author, text and timestamp of the quoted tweet, `None` once it is deleted
+    pub quoted_tweet: Option<Box<Tweet>>,
 }
 
 impl Default for Tweet {
@@ -19,15 +33,46 @@ impl Default for Tweet {
             tweet_id: Default::default(),
             user_id: Default::default(),
             tweet_text: Default::default(),
+            like_count: Default::default(),
+            retweeted_by: Default::default(),
+            retweet_ts: Default::default(),
+            in_reply_to: Default::default(),
+            conversation_id: Default::default(),
+            quoted_tweet_id: Default::default(),
+            quoted_tweet: Default::default(),
         }
     }
 }
 
 impl FromRedisValue for Tweet {
+    /// Tweets are read from Redis as a JSON object of the tweet fields
     fn from_redis_value(v: String) -> Self {
+        let fields: HashMap<String, String> = serde_json::from_str(&v).unwrap();
+        let field = |name: &str| fields.get(name).map(String::as_str);
+        let id = |name: &str| field(name).and_then(|id| id.parse().ok());
+        let ts = |name: &str| field(name).and_then(from_unix_secs);
+        // The quoted tweet fields are missing once the original is deleted
+        let quoted_tweet = field("quoted_user_id").map(|_| {
+            Box::new(Tweet {
+                tweet_id: id("quoted_tweet_id").unwrap_or_default(),
+                user_id: id("quoted_user_id").unwrap_or_default(),
+                tweet_text: field("quoted_tweet_text").unwrap_or_default().to_string(),
+                tweet_ts: ts("quoted_tweet_ts").unwrap_or_else(SystemTime::now),
+                ..Default::default()
+            })
+        });
         Tweet {
-            tweet_text: v,
-            ..Default::default()
+            tweet_id: id("tweet_id").unwrap_or_default(),
+            user_id: id("user_id").unwrap_or_default(),
+            tweet_text: field("tweet_text").unwrap_or_default().to_string(),
+            tweet_ts: ts("tweet_ts").unwrap_or_else(SystemTime::now),
+            like_count: field("like_count").and_then(|c| c.parse().ok()),
+            retweeted_by: id("retweeted_by"),
+            retweet_ts: ts("retweet_ts"),
+            in_reply_to: id("in_reply_to"),
+            conversation_id: id("conversation_id"),
+            quoted_tweet_id: id("quoted_tweet_id"),
+            quoted_tweet,
         }
     }
 }
@@ -39,10 +84,36 @@ impl FromPostgresRow for Tweet {
             user_id: r.get(1),
             tweet_text: r.get(2),
             tweet_ts: r.get(3),
+            like_count: r.try_get(4).ok(),
+            retweeted_by: r.try_get(5).ok().flatten(),
+            retweet_ts: r.try_get(6).ok().flatten(),
+            in_reply_to: r.try_get(7).ok().flatten(),
+            conversation_id: r.try_get(8).ok().flatten(),
+            quoted_tweet_id: r.try_get(9).ok().flatten(),
+            // Left joined, so the columns are NULL once the quoted tweet is deleted
+            quoted_tweet: r
+                .try_get::<_, Option<Identifier>>(10)
+                .ok()
+                .flatten()
+                .map(|user_id| {
+                    Box::new(Tweet {
+                        tweet_id: r.get(9),
+                        user_id,
+                        tweet_text: r.get(11),
+                        tweet_ts: r.get(12),
+                        ..Default::default()
+                    })
+                }),
         }
     }
 }
 
+fn from_unix_secs(secs: &str) -> Option<UnixTimestamp> {
+    secs.parse::<u64>()
+        .ok()
+        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+}
+
 impl Tweet {
     pub fn author(&self) -> Identifier {
         self.user_id
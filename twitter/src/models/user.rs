@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::misc::{Identifier, UnixTimestamp};
+use crate::structures::{FromPostgresRow, FromRedisValue};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct User {
+    user_id: Identifier,
+    pub handle: String,
+    pub display_name: String,
+    pub bio: String,
+    pub created_ts: UnixTimestamp,
+    pub followers_count: i64,
+    pub following_count: i64,
+    /// Only approved followers see the tweets of protected accounts
+    pub protected: bool,
+}
+
+impl Default for User {
+    fn default() -> Self {
+        Self {
+            created_ts: SystemTime::now(),
+            user_id: Default::default(),
+            handle: Default::default(),
+            display_name: Default::default(),
+            bio: Default::default(),
+            followers_count: Default::default(),
+            following_count: Default::default(),
+            protected: Default::default(),
+        }
+    }
+}
+
+impl FromRedisValue for User {
+    /// Profiles are read from Redis as a JSON object of the profile hash fields
+    fn from_redis_value(v: String) -> Self {
+        let fields: HashMap<String, String> = serde_json::from_str(&v).unwrap();
+        let field = |name: &str| fields.get(name).cloned().unwrap_or_default();
+        User {
+            user_id: field("user_id").parse().unwrap_or_default(),
+            handle: field("handle"),
+            display_name: field("display_name"),
+            bio: field("bio"),
+            created_ts: UNIX_EPOCH
+                + Duration::from_secs(field("created_ts").parse().unwrap_or_default()),
+            followers_count: field("followers_count").parse().unwrap_or_default(),
+            following_count: field("following_count").parse().unwrap_or_default(),
+            protected: field("protected") == "true",
+        }
+    }
+}
+
+impl FromPostgresRow for User {
+    fn from_pg_row(r: tokio_postgres::Row) -> Self {
+        User {
+            user_id: r.get(0),
+            handle: r.get(1),
+            display_name: r.get(2),
+            bio: r.get(3),
+            created_ts: r.get(4),
+            followers_count: r.get(5),
+            following_count: r.get(6),
+            protected: r.get(7),
+        }
+    }
+}
+
+impl User {
+    pub fn id(&self) -> Identifier {
+        self.user_id
+    }
+
+    #[allow(dead_code)]
+    pub fn partial_new(user_id: Identifier, handle: String, display_name: String) -> User {
+        User {
+            user_id,
+            handle,
+            display_name,
+            ..Default::default()
+        }
+    }
+}
@@ -1 +1,2 @@
-INSERT INTO Tweets (user_id, tweet_text) VALUES ($1, $2), ($3, $4), ($5, $6), ($7, $8), ($9, $10)
\ No newline at end of file
+-- VALUES tuples are generated for the size of each batch
+INSERT INTO Tweets (user_id, tweet_text) VALUES
\ No newline at end of file
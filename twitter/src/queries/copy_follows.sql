@@ -0,0 +1 @@
+COPY Follows (from_id, to_id) FROM STDIN BINARY
\ No newline at end of file
@@ -0,0 +1 @@
+COPY Tweets (user_id, tweet_text) FROM STDIN BINARY
\ No newline at end of file
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS schema_migrations (
+	version INT NOT NULL,
+	name VARCHAR(255) NOT NULL,
+	checksum VARCHAR(64) NOT NULL,
+	applied_ts TIMESTAMP DEFAULT current_timestamp,
+	PRIMARY KEY(version)
+)
@@ -0,0 +1 @@
+DELETE FROM Blocks WHERE user_id = ($1) AND blocked_id = ($2)
\ No newline at end of file
@@ -0,0 +1 @@
+DELETE FROM Follows WHERE from_id = ($1) AND to_id = ($2) AND status = 'pending'
\ No newline at end of file
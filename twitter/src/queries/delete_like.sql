@@ -0,0 +1 @@
+DELETE FROM Likes WHERE user_id = ($1) AND tweet_id = ($2)
\ No newline at end of file
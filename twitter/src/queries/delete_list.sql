@@ -0,0 +1 @@
+DELETE FROM Lists WHERE owner_id = ($1) AND list_name = ($2)
\ No newline at end of file
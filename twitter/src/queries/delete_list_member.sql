@@ -0,0 +1,6 @@
+DELETE FROM ListMembers AS m
+USING Lists AS l
+WHERE m.list_id = l.list_id
+AND l.owner_id = ($1)
+AND l.list_name = ($2)
+AND m.user_id = ($3);
\ No newline at end of file
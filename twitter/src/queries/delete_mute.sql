@@ -0,0 +1 @@
+DELETE FROM Mutes WHERE user_id = ($1) AND muted_id = ($2)
\ No newline at end of file
@@ -0,0 +1 @@
+DELETE FROM ScheduledTweets WHERE user_id = ($1) AND scheduled_id = ($2)
\ No newline at end of file
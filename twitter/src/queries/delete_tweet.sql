@@ -0,0 +1 @@
+DELETE FROM Tweets WHERE tweet_id = ($1) AND user_id = ($2)
\ No newline at end of file
@@ -0,0 +1,7 @@
+-- Blocking drops the follows in both directions
+WITH unfollowed AS (
+       DELETE FROM Follows
+       WHERE (from_id = ($1) AND to_id = ($2))
+       OR (from_id = ($2) AND to_id = ($1))
+)
+INSERT INTO Blocks (user_id, blocked_id) VALUES ($1, $2) ON CONFLICT DO NOTHING;
\ No newline at end of file
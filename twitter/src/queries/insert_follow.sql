@@ -1 +1 @@
-INSERT INTO Follows (from_id, to_id) VALUES ($1, $2)
\ No newline at end of file
+INSERT INTO Follows (from_id, to_id) VALUES ($1, $2) ON CONFLICT DO NOTHING
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO Lists (owner_id, list_name) VALUES ($1, $2)
\ No newline at end of file
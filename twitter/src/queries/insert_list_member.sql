@@ -0,0 +1,10 @@
+-- Adding a member twice is a no-op, but the list must exist
+INSERT INTO ListMembers (list_id, user_id)
+SELECT  l.list_id
+       ,($3)
+FROM Lists AS l
+WHERE l.owner_id = ($1)
+AND l.list_name = ($2)
+-- No-op update so the existing member still counts as a matched row
+ON CONFLICT (list_id, user_id) DO UPDATE
+SET user_id = EXCLUDED.user_id;
\ No newline at end of file
@@ -0,0 +1,12 @@
+-- Start the conversation on the first message
+WITH conversation AS (
+       INSERT INTO Conversations (user_a, user_b)
+       VALUES (LEAST(($1)::INT, ($2)::INT), GREATEST(($1)::INT, ($2)::INT))
+       -- No-op update so the existing conversation id is returned
+       ON CONFLICT (user_a, user_b) DO UPDATE
+       SET user_a = EXCLUDED.user_a
+       RETURNING conversation_id
+)
+INSERT INTO Messages (conversation_id, sender_id, message_text)
+SELECT conversation_id, ($1), ($3)
+FROM conversation;
\ No newline at end of file
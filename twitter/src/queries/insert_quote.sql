@@ -0,0 +1,7 @@
+-- Only existing tweets can be quoted
+INSERT INTO Tweets (user_id, tweet_text, quoted_tweet_id)
+SELECT  ($1)
+       ,($2)
+       ,q.tweet_id
+FROM Tweets AS q
+WHERE q.tweet_id = ($3);
\ No newline at end of file
@@ -0,0 +1,8 @@
+-- Reply inherits the conversation of the tweet it replies to
+INSERT INTO Tweets (user_id, tweet_text, in_reply_to, conversation_id)
+SELECT  ($1)
+       ,($2)
+       ,p.tweet_id
+       ,COALESCE(p.conversation_id, p.tweet_id)
+FROM Tweets AS p
+WHERE p.tweet_id = ($3);
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO ScheduledTweets (user_id, tweet_text, publish_at) VALUES ($1, $2, $3)
\ No newline at end of file
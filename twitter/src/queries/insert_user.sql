@@ -0,0 +1 @@
+INSERT INTO Users (user_id, handle, display_name, bio) VALUES ($1, $2, $3, $4)
\ No newline at end of file
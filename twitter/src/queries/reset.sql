@@ -1,2 +0,0 @@
-DROP TABLE IF EXISTS Tweets;
-DROP TABLE IF EXISTS Follows;
\ No newline at end of file
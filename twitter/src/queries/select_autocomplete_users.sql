@@ -0,0 +1,24 @@
+-- Get users whose handle starts with the prefix, most followed first
+SELECT  u.user_id
+       ,u.handle
+       ,u.display_name
+       ,u.bio
+       ,u.created_ts
+       -- Pending follow requests are not counted
+       ,(SELECT COUNT(*) FROM Follows WHERE Follows.to_id = u.user_id AND Follows.status = 'approved') AS followers_count
+       ,(SELECT COUNT(*) FROM Follows WHERE Follows.from_id = u.user_id AND Follows.status = 'approved') AS following_count
+       ,u.protected
+FROM (
+       -- Only the first 50 handles in lexicographic order are ranked, or as many as requested
+       SELECT *
+       FROM Users
+       -- The prefix is matched literally, so the escape character goes first, then the
+       -- LIKE wildcards
+       WHERE LOWER(handle) LIKE REPLACE(
+              REPLACE(REPLACE(LOWER($1), '\', '\\'), '%', '\%'), '_', '\_'
+       ) || '%' ESCAPE '\'
+       ORDER BY LOWER(handle)
+       LIMIT GREATEST(50, ($2)::BIGINT)
+) AS u
+ORDER BY followers_count DESC, LOWER(u.handle)
+LIMIT ($2);
\ No newline at end of file
@@ -0,0 +1,26 @@
+-- Get the tweets bookmarked by the user, newest bookmark first
+SELECT  t.tweet_id
+       ,t.user_id
+       ,t.tweet_text
+       ,t.tweet_ts
+       ,(SELECT COUNT(*) FROM Likes WHERE Likes.tweet_id = t.tweet_id) AS like_count
+       ,NULL::INT AS retweeted_by
+       ,NULL::TIMESTAMP AS retweet_ts
+       ,t.in_reply_to
+       ,COALESCE(t.conversation_id, t.tweet_id) AS conversation_id
+       ,t.quoted_tweet_id
+       ,q.user_id AS quoted_user_id
+       ,q.tweet_text AS quoted_tweet_text
+       ,q.tweet_ts AS quoted_tweet_ts
+FROM Bookmarks AS b
+INNER JOIN Tweets AS t
+ON t.tweet_id = b.tweet_id
+-- Embed the quoted tweet, if it still exists and the viewer can see it
+LEFT JOIN Tweets AS q
+ON q.tweet_id = t.quoted_tweet_id
+AND can_view(($1), q.user_id)
+WHERE b.user_id = ($1)
+-- Protected tweets only show up for approved followers, e.g. not after unfollowing
+AND can_view(($1), t.user_id)
+ORDER BY b.bookmark_ts DESC, b.tweet_id DESC
+LIMIT ($2) OFFSET ($3);
\ No newline at end of file
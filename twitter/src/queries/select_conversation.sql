@@ -0,0 +1,45 @@
+-- Get the reply tree of the tweet conversation, depth first from its root. Once the root is
+-- deleted, its replies are the top level of the tree
+WITH RECURSIVE root AS (
+       SELECT COALESCE(conversation_id, tweet_id) AS tweet_id FROM Tweets WHERE tweet_id = ($1)
+), thread AS (
+       SELECT  t.tweet_id
+              ,0 AS depth
+              ,ARRAY[t.tweet_id] AS path
+       FROM Tweets AS t
+       INNER JOIN root
+       ON t.tweet_id = root.tweet_id
+       OR (t.conversation_id = root.tweet_id AND t.in_reply_to IS NULL)
+       UNION ALL
+       SELECT  r.tweet_id
+              ,thread.depth + 1
+              ,thread.path || r.tweet_id
+       FROM Tweets AS r
+       INNER JOIN thread
+       ON r.in_reply_to = thread.tweet_id
+       -- Stop at the depth limit
+       WHERE thread.depth < ($2)
+)
+SELECT  t.tweet_id
+       ,t.user_id
+       ,t.tweet_text
+       ,t.tweet_ts
+       ,(SELECT COUNT(*) FROM Likes WHERE Likes.tweet_id = t.tweet_id) AS like_count
+       ,NULL::INT AS retweeted_by
+       ,NULL::TIMESTAMP AS retweet_ts
+       ,t.in_reply_to
+       ,COALESCE(t.conversation_id, t.tweet_id) AS conversation_id
+       ,t.quoted_tweet_id
+       ,q.user_id AS quoted_user_id
+       ,q.tweet_text AS quoted_tweet_text
+       ,q.tweet_ts AS quoted_tweet_ts
+FROM thread
+INNER JOIN Tweets AS t
+ON t.tweet_id = thread.tweet_id
+-- Embed the quoted tweet, if it still exists and the viewer can see it
+LEFT JOIN Tweets AS q
+ON q.tweet_id = t.quoted_tweet_id
+AND can_view(($3), q.user_id)
+-- Protected tweets only show up for approved followers, their replies stay in the thread
+WHERE can_view(($3), t.user_id)
+ORDER BY thread.path;
\ No newline at end of file
@@ -0,0 +1,16 @@
+-- Get the conversations of a user with their last message, most recently active first
+SELECT  CASE WHEN c.user_a = ($1) THEN c.user_b ELSE c.user_a END AS user_id
+       ,m.sender_id
+       ,m.message_text
+       ,m.message_ts
+FROM Conversations AS c
+JOIN LATERAL (
+       SELECT message_id, sender_id, message_text, message_ts
+       FROM Messages
+       WHERE Messages.conversation_id = c.conversation_id
+       ORDER BY message_id DESC
+       LIMIT 1
+) AS m ON TRUE
+WHERE c.user_a = ($1) OR c.user_b = ($1)
+ORDER BY m.message_id DESC
+LIMIT ($2);
\ No newline at end of file
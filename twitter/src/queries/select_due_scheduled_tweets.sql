@@ -0,0 +1,11 @@
+-- Get the scheduled tweets due for publishing, oldest first. Rows are locked until the
+-- transaction ends, so concurrent schedulers skip the tweets another one is publishing
+SELECT  s.scheduled_id
+       ,s.user_id
+       ,s.tweet_text
+       ,s.publish_at
+FROM ScheduledTweets AS s
+WHERE s.publish_at <= ($1)
+ORDER BY s.publish_at, s.scheduled_id
+LIMIT ($2)
+FOR UPDATE SKIP LOCKED;
\ No newline at end of file
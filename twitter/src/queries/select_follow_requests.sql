@@ -0,0 +1,10 @@
+-- Get the pending follow requests of a protected account, oldest first
+SELECT  follow_id
+       ,from_id
+       ,to_id
+       ,follow_ts
+       ,status
+FROM Follows
+WHERE to_id = ($1)
+AND status = 'pending'
+ORDER BY follow_ts, follow_id;
\ No newline at end of file
@@ -0,0 +1,27 @@
+-- Get tweets tagged with the hashtag
+SELECT  t.tweet_id
+       ,t.user_id
+       ,t.tweet_text
+       ,t.tweet_ts
+       ,(SELECT COUNT(*) FROM Likes WHERE Likes.tweet_id = t.tweet_id) AS like_count
+       ,NULL::INT AS retweeted_by
+       ,NULL::TIMESTAMP AS retweet_ts
+       ,t.in_reply_to
+       ,COALESCE(t.conversation_id, t.tweet_id) AS conversation_id
+       ,t.quoted_tweet_id
+       ,q.user_id AS quoted_user_id
+       ,q.tweet_text AS quoted_tweet_text
+       ,q.tweet_ts AS quoted_tweet_ts
+FROM Hashtags AS h
+INNER JOIN Tweets AS t
+ON t.tweet_id = h.tweet_id
+-- Embed the quoted tweet, if it still exists and the viewer can see it
+LEFT JOIN Tweets AS q
+ON q.tweet_id = t.quoted_tweet_id
+AND can_view(($4), q.user_id)
+WHERE h.tag = LOWER($1)
+-- Protected tweets only show up for approved followers
+AND can_view(($4), t.user_id)
+-- Most recent tweets first
+ORDER BY t.tweet_ts DESC
+LIMIT ($2) OFFSET ($3);
\ No newline at end of file
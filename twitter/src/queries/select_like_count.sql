@@ -0,0 +1 @@
+SELECT COUNT(*) FROM Likes WHERE tweet_id = ($1)
\ No newline at end of file
@@ -0,0 +1,6 @@
+-- Get users who liked a tweet
+SELECT user_id
+FROM Likes
+WHERE tweet_id = ($1)
+ORDER BY user_id
+LIMIT ($2) OFFSET ($3);
\ No newline at end of file
@@ -0,0 +1,40 @@
+-- Get tweets of the list members
+SELECT  t.tweet_id
+       ,t.user_id
+       ,t.tweet_text
+       ,t.tweet_ts
+       ,(SELECT COUNT(*) FROM Likes WHERE Likes.tweet_id = t.tweet_id) AS like_count
+       ,NULL::INT AS retweeted_by
+       ,NULL::TIMESTAMP AS retweet_ts
+       ,t.in_reply_to
+       ,COALESCE(t.conversation_id, t.tweet_id) AS conversation_id
+       ,t.quoted_tweet_id
+       ,q.user_id AS quoted_user_id
+       ,q.tweet_text AS quoted_tweet_text
+       ,q.tweet_ts AS quoted_tweet_ts
+FROM Tweets AS t
+INNER JOIN ListMembers AS m
+-- Join list members with tweets
+ON m.user_id = t.user_id
+INNER JOIN Lists AS l
+ON l.list_id = m.list_id
+AND l.owner_id = ($1)
+AND l.list_name = ($2)
+-- Embed the quoted tweet, if it still exists and the viewer can see it
+LEFT JOIN Tweets AS q
+ON q.tweet_id = t.quoted_tweet_id
+AND can_view(($1), q.user_id)
+-- Protected members only show up for the owner if it is an approved follower
+WHERE can_view(($1), t.user_id)
+-- Hide tweets of muted users, like on the home timeline
+AND NOT EXISTS (
+       SELECT 1
+       FROM Mutes
+       WHERE Mutes.user_id = ($1)
+       AND Mutes.muted_id = t.user_id
+)
+-- and of users blocking or blocked by the owner
+AND NOT is_blocked(($1), t.user_id)
+-- Most recent tweets first
+ORDER BY t.tweet_ts DESC, t.tweet_id DESC
+LIMIT ($3) OFFSET ($4);
\ No newline at end of file
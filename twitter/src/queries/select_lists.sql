@@ -0,0 +1,8 @@
+-- Get the lists of a user with their member counter, oldest first
+SELECT  l.owner_id
+       ,l.list_name
+       ,(SELECT COUNT(*) FROM ListMembers WHERE ListMembers.list_id = l.list_id) AS member_count
+       ,l.created_ts
+FROM Lists AS l
+WHERE l.owner_id = ($1)
+ORDER BY l.created_ts, l.list_id;
\ No newline at end of file
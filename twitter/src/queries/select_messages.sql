@@ -0,0 +1,14 @@
+-- Get a page of the messages between two users older than the cursor, newest first
+SELECT  m.message_id
+       ,m.sender_id
+       ,CASE WHEN m.sender_id = c.user_a THEN c.user_b ELSE c.user_a END AS recipient_id
+       ,m.message_text
+       ,m.message_ts
+FROM Conversations AS c
+JOIN Messages AS m
+ON m.conversation_id = c.conversation_id
+WHERE c.user_a = LEAST(($1)::INT, ($2)::INT)
+AND c.user_b = GREATEST(($1)::INT, ($2)::INT)
+AND m.message_id < ($3)
+ORDER BY m.message_id DESC
+LIMIT ($4);
\ No newline at end of file
@@ -0,0 +1,8 @@
+-- Get the pending scheduled tweets of a user, soonest first
+SELECT  s.scheduled_id
+       ,s.user_id
+       ,s.tweet_text
+       ,s.publish_at
+FROM ScheduledTweets AS s
+WHERE s.user_id = ($1)
+ORDER BY s.publish_at, s.scheduled_id;
\ No newline at end of file
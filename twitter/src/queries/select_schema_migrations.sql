@@ -0,0 +1,6 @@
+-- Migrations already applied to the database
+SELECT  version
+       ,name
+       ,checksum
+FROM schema_migrations
+ORDER BY version ASC;
\ No newline at end of file
@@ -0,0 +1,28 @@
+-- Get tweets matching the search query, most relevant first
+SELECT  t.tweet_id
+       ,t.user_id
+       ,t.tweet_text
+       ,t.tweet_ts
+       ,(SELECT COUNT(*) FROM Likes WHERE Likes.tweet_id = t.tweet_id) AS like_count
+       ,NULL::INT AS retweeted_by
+       ,NULL::TIMESTAMP AS retweet_ts
+       ,t.in_reply_to
+       ,COALESCE(t.conversation_id, t.tweet_id) AS conversation_id
+       ,t.quoted_tweet_id
+       ,q.user_id AS quoted_user_id
+       ,q.tweet_text AS quoted_tweet_text
+       ,q.tweet_ts AS quoted_tweet_ts
+FROM Tweets AS t
+-- The query is normalized to `term term OR term`, terms being lowercased letters and digits,
+-- e.g. `rust tokio OR redis` becomes `rust&tokio|redis`
+CROSS JOIN to_tsquery('simple', replace(replace(($1), ' OR ', '|'), ' ', '&')) AS query
+-- Embed the quoted tweet, if it still exists and the viewer can see it
+LEFT JOIN Tweets AS q
+ON q.tweet_id = t.quoted_tweet_id
+AND can_view(($4), q.user_id)
+WHERE t.search_vector @@ query
+-- Protected tweets only show up for approved followers
+AND can_view(($4), t.user_id)
+-- Ties are broken by recency so pages are stable
+ORDER BY ts_rank(t.search_vector, query) DESC, t.tweet_id DESC
+LIMIT ($2) OFFSET ($3);
\ No newline at end of file
@@ -0,0 +1,8 @@
+-- Get the most used hashtags over the last ($1) seconds
+SELECT  h.tag
+       ,COUNT(*) AS tweet_count
+FROM Hashtags AS h
+WHERE h.tweet_ts >= LOCALTIMESTAMP - (($1)::BIGINT * INTERVAL '1 second')
+GROUP BY h.tag
+ORDER BY tweet_count DESC, h.tag
+LIMIT ($2);
\ No newline at end of file
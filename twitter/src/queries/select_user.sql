@@ -0,0 +1,12 @@
+-- Get user profile with its follow counters
+SELECT  u.user_id
+       ,u.handle
+       ,u.display_name
+       ,u.bio
+       ,u.created_ts
+       -- Pending follow requests are not counted
+       ,(SELECT COUNT(*) FROM Follows WHERE Follows.to_id = u.user_id AND Follows.status = 'approved') AS followers_count
+       ,(SELECT COUNT(*) FROM Follows WHERE Follows.from_id = u.user_id AND Follows.status = 'approved') AS following_count
+       ,u.protected
+FROM Users AS u
+WHERE u.user_id = ($1);
@@ -1,14 +1,59 @@
--- Get tweets of all user followers
+-- Get tweets and retweets of all user followers
+WITH entries AS (
+       SELECT  t.tweet_id
+              ,NULL::INT AS retweeted_by
+              ,t.tweet_ts AS entry_ts
+       FROM Tweets AS t
+       INNER JOIN Follows
+       -- Join follows with tweets 
+       ON Follows.to_id = t.user_id 
+       -- Followed by user, protected accounts only reach approved followers
+       AND Follows.from_id = ($1)
+       AND Follows.status = 'approved'
+       UNION ALL
+       SELECT  r.tweet_id
+              ,r.user_id AS retweeted_by
+              ,r.retweet_ts AS entry_ts
+       FROM Retweets AS r
+       INNER JOIN Follows
+       -- Join follows with retweets
+       ON Follows.to_id = r.user_id
+       AND Follows.from_id = ($1)
+       AND Follows.status = 'approved'
+)
 SELECT  t.tweet_id
        ,t.user_id
        ,t.tweet_text
        ,t.tweet_ts
-FROM Tweets AS t
-INNER JOIN Follows
--- Join follows with tweets 
-ON Follows.to_id = t.user_id 
--- Followed by user
-AND Follows.from_id = ($1)
--- 10 most recent tweets
-ORDER BY t.tweet_ts DESC
+       ,(SELECT COUNT(*) FROM Likes WHERE Likes.tweet_id = t.tweet_id) AS like_count
+       ,e.retweeted_by
+       ,CASE WHEN e.retweeted_by IS NULL THEN NULL ELSE e.entry_ts END AS retweet_ts
+       ,t.in_reply_to
+       ,COALESCE(t.conversation_id, t.tweet_id) AS conversation_id
+       ,t.quoted_tweet_id
+       ,q.user_id AS quoted_user_id
+       ,q.tweet_text AS quoted_tweet_text
+       ,q.tweet_ts AS quoted_tweet_ts
+FROM entries AS e
+INNER JOIN Tweets AS t
+ON t.tweet_id = e.tweet_id
+-- Embed the quoted tweet, if it still exists and the viewer can see it
+LEFT JOIN Tweets AS q
+ON q.tweet_id = t.quoted_tweet_id
+AND can_view(($1), q.user_id)
+-- Hide tweets and retweets of muted users
+WHERE NOT EXISTS (
+       SELECT 1
+       FROM Mutes
+       WHERE Mutes.user_id = ($1)
+       AND Mutes.muted_id IN (t.user_id, e.retweeted_by)
+)
+-- and of users blocking or blocked by the user
+AND NOT is_blocked(($1), t.user_id)
+AND (e.retweeted_by IS NULL OR NOT is_blocked(($1), e.retweeted_by))
+-- Retweets of protected tweets, e.g. made before the account was protected, only show up
+-- for its approved followers
+AND can_view(($1), t.user_id)
+-- 10 most recent entries
+ORDER BY e.entry_ts DESC
 LIMIT ($2) OFFSET ($3);
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE Follows SET status = 'approved' WHERE from_id = ($1) AND to_id = ($2) AND status = 'pending'
\ No newline at end of file
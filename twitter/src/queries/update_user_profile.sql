@@ -0,0 +1 @@
+UPDATE Users SET handle = ($2), display_name = ($3), bio = ($4) WHERE user_id = ($1)
\ No newline at end of file
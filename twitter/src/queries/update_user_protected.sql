@@ -0,0 +1,9 @@
+-- Pending follow requests are approved once the account is no longer protected
+WITH approved AS (
+       UPDATE Follows
+       SET status = 'approved'
+       WHERE to_id = ($1)
+       AND status = 'pending'
+       AND NOT ($2)
+)
+UPDATE Users SET protected = ($2) WHERE user_id = ($1);
\ No newline at end of file
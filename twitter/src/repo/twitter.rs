@@ -1,40 +1,41 @@
+use std::collections::HashSet;
+
 use crate::{
     errors::DatabaseError,
     keywords,
-    misc::Identifier,
-    models::Tweet,
+    misc::{Identifier, UnixTimestamp},
+    models::{Conversation, Follow, List, Message, ScheduledTweet, Trend, Tweet, User},
     storage::{Database, DatabaseRef, Transaction},
     structures::{Document, SimpleTransaction, SuperValue},
+    utils::normalize_search_query,
 };
-use std::cell::Cell;
 
+#[derive(Clone)]
 pub struct TwitterRepository {
-    pub ds_ref: Cell<DatabaseRef>,
+    pub ds_ref: DatabaseRef,
 }
 
 impl TwitterRepository {
     pub fn new(ds_ref: DatabaseRef) -> Self {
-        TwitterRepository {
-            ds_ref: Cell::new(ds_ref),
-        }
+        TwitterRepository { ds_ref }
     }
 
-    fn db(&mut self) -> &mut Database {
-        &mut self.ds_ref.get_mut().db
+    fn db(&self) -> &Database {
+        &self.ds_ref.db
     }
 
-    pub async fn tx(&mut self) -> Transaction {
+    pub async fn tx(&self) -> Transaction {
         self.db().transaction(false).await.unwrap()
     }
 
-    pub async fn mut_tx(&mut self) -> Transaction {
+    pub async fn mut_tx(&self) -> Transaction {
         self.db().transaction(true).await.unwrap()
     }
 }
 
 impl TwitterRepository {
     pub async fn create_tweet(
-        &mut self,
+        &self,
         tx: &mut Transaction,
         user_id: Identifier,
         text: String,
@@ -49,8 +50,88 @@ impl TwitterRepository {
         Ok(())
     }
 
+    pub async fn create_reply(
+        &self,
+        tx: &mut Transaction,
+        user_id: Identifier,
+        text: String,
+        in_reply_to: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.set(
+            Document::Tweets,
+            vec![
+                SuperValue::Integer(user_id),
+                SuperValue::String(text),
+                SuperValue::Integer(in_reply_to),
+            ],
+            keywords!("tag" => String::from("reply")),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn create_quote(
+        &self,
+        tx: &mut Transaction,
+        user_id: Identifier,
+        text: String,
+        quoted_tweet_id: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.set(
+            Document::Tweets,
+            vec![
+                SuperValue::Integer(user_id),
+                SuperValue::String(text),
+                SuperValue::Integer(quoted_tweet_id),
+            ],
+            keywords!("tag" => String::from("quote")),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_conversation(
+        &self,
+        tx: &Transaction,
+        tweet_id: Identifier,
+        max_depth: i32,
+        viewer: Identifier,
+    ) -> Result<Vec<Tweet>, DatabaseError> {
+        let tweets: Vec<Tweet> = tx
+            .get(
+                Document::Tweets,
+                vec![
+                    SuperValue::Integer(tweet_id),
+                    SuperValue::Integer(max_depth),
+                    SuperValue::Integer(viewer),
+                ],
+                keywords!("tag" => String::from("conversation")),
+            )
+            .await?;
+
+        Ok(tweets)
+    }
+
+    pub async fn delete_tweet(
+        &self,
+        tx: &mut Transaction,
+        tweet_id: Identifier,
+        author: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.del(
+            Document::Tweets,
+            vec![SuperValue::Integer(tweet_id), SuperValue::Integer(author)],
+            keywords!(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn batch_create_tweets(
-        &mut self,
+        &self,
         tx: &mut Transaction,
         tweets: Vec<Tweet>,
     ) -> Result<(), DatabaseError> {
@@ -67,13 +148,53 @@ impl TwitterRepository {
         Ok(())
     }
 
+    pub async fn bulk_create_tweets(
+        &self,
+        tx: &mut Transaction,
+        tweets: Vec<Tweet>,
+    ) -> Result<(), DatabaseError> {
+        let mut params = vec![];
+        for tweet in tweets {
+            let sub_params = vec![
+                SuperValue::Integer(tweet.author()),
+                SuperValue::String(tweet.tweet_text),
+            ];
+            params.push(sub_params);
+        }
+        tx.bulk_set(Document::Tweets, params).await?;
+
+        Ok(())
+    }
+
     pub async fn create_follow(
-        &mut self,
+        &self,
         tx: &mut Transaction,
         from: Identifier,
         to: Identifier,
+        backfill: Option<usize>,
     ) -> Result<(), DatabaseError> {
+        let mut keywords = keywords!();
+        if let Some(limit) = backfill {
+            // Only materialized timelines need the followee's recent tweets copied over
+            keywords.insert("backfill", limit.to_string());
+        }
         tx.set(
+            Document::Follows,
+            vec![SuperValue::Integer(from), SuperValue::Integer(to)],
+            keywords,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_follow(
+        &self,
+        tx: &mut Transaction,
+        from: Identifier,
+        to: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.del(
             Document::Follows,
             vec![SuperValue::Integer(from), SuperValue::Integer(to)],
             keywords!(),
@@ -83,8 +204,84 @@ impl TwitterRepository {
         Ok(())
     }
 
+    pub async fn approve_follow_request(
+        &self,
+        tx: &mut Transaction,
+        requester: Identifier,
+        owner: Identifier,
+        backfill: Option<usize>,
+    ) -> Result<(), DatabaseError> {
+        let mut keywords = keywords!("tag" => String::from("approve"));
+        if let Some(limit) = backfill {
+            keywords.insert("backfill", limit.to_string());
+        }
+        tx.set(
+            Document::Follows,
+            vec![SuperValue::Integer(requester), SuperValue::Integer(owner)],
+            keywords,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn reject_follow_request(
+        &self,
+        tx: &mut Transaction,
+        requester: Identifier,
+        owner: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.del(
+            Document::Follows,
+            vec![SuperValue::Integer(requester), SuperValue::Integer(owner)],
+            keywords!("tag" => String::from("request")),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_follow_requests(
+        &self,
+        tx: &Transaction,
+        owner: Identifier,
+    ) -> Result<Vec<Follow>, DatabaseError> {
+        let requests: Vec<Follow> = tx
+            .get(
+                Document::Follows,
+                vec![SuperValue::Integer(owner)],
+                keywords!("tag" => String::from("requests")),
+            )
+            .await?;
+
+        Ok(requests)
+    }
+
+    pub async fn bulk_create_follows(
+        &self,
+        tx: &mut Transaction,
+        follows: Vec<Follow>,
+    ) -> Result<(), DatabaseError> {
+        let mut params = vec![];
+        // COPY cannot skip conflicting rows, so duplicates are dropped beforehand
+        let mut seen = HashSet::new();
+        for follow in follows {
+            if !seen.insert((follow.from(), follow.to())) {
+                continue;
+            }
+            let sub_params = vec![
+                SuperValue::Integer(follow.from()),
+                SuperValue::Integer(follow.to()),
+            ];
+            params.push(sub_params);
+        }
+        tx.bulk_set(Document::Follows, params).await?;
+
+        Ok(())
+    }
+
     pub async fn get_timeline(
-        &mut self,
+        &self,
         tx: &Transaction,
         user_id: Identifier,
     ) -> Result<Vec<Tweet>, DatabaseError> {
@@ -102,4 +299,685 @@ impl TwitterRepository {
 
         Ok(tweets)
     }
+
+    pub async fn get_mentions_timeline(
+        &self,
+        tx: &Transaction,
+        user_id: Identifier,
+    ) -> Result<Vec<Tweet>, DatabaseError> {
+        let tweets: Vec<Tweet> = tx
+            .get(
+                Document::Tweets,
+                vec![
+                    SuperValue::Integer(user_id),
+                    SuperValue::BigInteger(10),
+                    SuperValue::BigInteger(0),
+                ],
+                keywords!("tag" => String::from("mentions_timeline")),
+            )
+            .await?;
+
+        Ok(tweets)
+    }
+
+    pub async fn get_hashtag_timeline(
+        &self,
+        tx: &Transaction,
+        tag: String,
+        viewer: Identifier,
+    ) -> Result<Vec<Tweet>, DatabaseError> {
+        let tweets: Vec<Tweet> = tx
+            .get(
+                Document::Tweets,
+                vec![
+                    SuperValue::String(tag),
+                    SuperValue::BigInteger(10),
+                    SuperValue::BigInteger(0),
+                    SuperValue::Integer(viewer),
+                ],
+                keywords!("tag" => String::from("hashtag_timeline")),
+            )
+            .await?;
+
+        Ok(tweets)
+    }
+
+    pub async fn search_tweets(
+        &self,
+        tx: &Transaction,
+        query: String,
+        limit: i64,
+        offset: i64,
+        viewer: Identifier,
+    ) -> Result<Vec<Tweet>, DatabaseError> {
+        let tweets: Vec<Tweet> = tx
+            .get(
+                Document::Tweets,
+                vec![
+                    SuperValue::String(normalize_search_query(&query)),
+                    SuperValue::BigInteger(limit),
+                    SuperValue::BigInteger(offset),
+                    SuperValue::Integer(viewer),
+                ],
+                keywords!("tag" => String::from("search")),
+            )
+            .await?;
+
+        Ok(tweets)
+    }
+
+    pub async fn get_trending(
+        &self,
+        tx: &Transaction,
+        window_secs: i64,
+        limit: i64,
+    ) -> Result<Vec<Trend>, DatabaseError> {
+        let trends: Vec<Trend> = tx
+            .get(
+                Document::Hashtags,
+                vec![
+                    SuperValue::BigInteger(window_secs),
+                    SuperValue::BigInteger(limit),
+                ],
+                keywords!("tag" => String::from("trending")),
+            )
+            .await?;
+
+        Ok(trends)
+    }
+
+    pub async fn create_user(&self, tx: &mut Transaction, user: User) -> Result<(), DatabaseError> {
+        tx.set(
+            Document::Users,
+            vec![
+                SuperValue::Integer(user.id()),
+                SuperValue::String(user.handle),
+                SuperValue::String(user.display_name),
+                SuperValue::String(user.bio),
+            ],
+            keywords!(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update_user(&self, tx: &mut Transaction, user: User) -> Result<(), DatabaseError> {
+        tx.set(
+            Document::Users,
+            vec![
+                SuperValue::Integer(user.id()),
+                SuperValue::String(user.handle),
+                SuperValue::String(user.display_name),
+                SuperValue::String(user.bio),
+            ],
+            keywords!("tag" => String::from("profile")),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_user_protected(
+        &self,
+        tx: &mut Transaction,
+        user_id: Identifier,
+        protected: bool,
+        backfill: Option<usize>,
+    ) -> Result<(), DatabaseError> {
+        let mut keywords = keywords!("tag" => String::from("protected"));
+        if let Some(limit) = backfill {
+            // Requests approved by making the account public are backfilled like new follows
+            keywords.insert("backfill", limit.to_string());
+        }
+        tx.set(
+            Document::Users,
+            vec![SuperValue::Integer(user_id), SuperValue::Bool(protected)],
+            keywords,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_user(
+        &self,
+        tx: &Transaction,
+        user_id: Identifier,
+    ) -> Result<Option<User>, DatabaseError> {
+        let users: Vec<User> = tx
+            .get(
+                Document::Users,
+                vec![SuperValue::Integer(user_id)],
+                keywords!("tag" => String::from("user")),
+            )
+            .await?;
+
+        Ok(users.into_iter().next())
+    }
+
+    pub async fn get_user_by_handle(
+        &self,
+        tx: &Transaction,
+        handle: String,
+    ) -> Result<Option<User>, DatabaseError> {
+        let users: Vec<User> = tx
+            .get(
+                Document::Users,
+                vec![SuperValue::String(handle)],
+                keywords!("tag" => String::from("user_by_handle")),
+            )
+            .await?;
+
+        Ok(users.into_iter().next())
+    }
+
+    pub async fn autocomplete_users(
+        &self,
+        tx: &Transaction,
+        prefix: String,
+        limit: i64,
+    ) -> Result<Vec<User>, DatabaseError> {
+        let users: Vec<User> = tx
+            .get(
+                Document::Users,
+                vec![SuperValue::String(prefix), SuperValue::BigInteger(limit)],
+                keywords!("tag" => String::from("autocomplete")),
+            )
+            .await?;
+
+        Ok(users)
+    }
+
+    pub async fn create_like(
+        &self,
+        tx: &mut Transaction,
+        user_id: Identifier,
+        tweet_id: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.set(
+            Document::Likes,
+            vec![SuperValue::Integer(user_id), SuperValue::Integer(tweet_id)],
+            keywords!(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_like(
+        &self,
+        tx: &mut Transaction,
+        user_id: Identifier,
+        tweet_id: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.del(
+            Document::Likes,
+            vec![SuperValue::Integer(user_id), SuperValue::Integer(tweet_id)],
+            keywords!(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_like_count(
+        &self,
+        tx: &Transaction,
+        tweet_id: Identifier,
+    ) -> Result<i64, DatabaseError> {
+        let counts: Vec<i64> = tx
+            .get(
+                Document::Likes,
+                vec![SuperValue::Integer(tweet_id)],
+                keywords!("tag" => String::from("like_count")),
+            )
+            .await?;
+
+        Ok(counts.into_iter().next().unwrap_or_default())
+    }
+
+    pub async fn get_liked_by(
+        &self,
+        tx: &Transaction,
+        tweet_id: Identifier,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Identifier>, DatabaseError> {
+        let users: Vec<Identifier> = tx
+            .get(
+                Document::Likes,
+                vec![
+                    SuperValue::Integer(tweet_id),
+                    SuperValue::BigInteger(limit),
+                    SuperValue::BigInteger(offset),
+                ],
+                keywords!("tag" => String::from("liked_by")),
+            )
+            .await?;
+
+        Ok(users)
+    }
+
+    pub async fn create_retweet(
+        &self,
+        tx: &mut Transaction,
+        user_id: Identifier,
+        tweet_id: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.set(
+            Document::Retweets,
+            vec![SuperValue::Integer(user_id), SuperValue::Integer(tweet_id)],
+            keywords!(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn create_message(
+        &self,
+        tx: &mut Transaction,
+        sender_id: Identifier,
+        recipient_id: Identifier,
+        text: String,
+    ) -> Result<(), DatabaseError> {
+        tx.set(
+            Document::Messages,
+            vec![
+                SuperValue::Integer(sender_id),
+                SuperValue::Integer(recipient_id),
+                SuperValue::String(text),
+            ],
+            keywords!(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_conversations(
+        &self,
+        tx: &Transaction,
+        user_id: Identifier,
+        limit: i64,
+    ) -> Result<Vec<Conversation>, DatabaseError> {
+        let conversations: Vec<Conversation> = tx
+            .get(
+                Document::Messages,
+                vec![SuperValue::Integer(user_id), SuperValue::BigInteger(limit)],
+                keywords!("tag" => String::from("conversations")),
+            )
+            .await?;
+
+        Ok(conversations)
+    }
+
+    pub async fn get_messages(
+        &self,
+        tx: &Transaction,
+        user_id: Identifier,
+        other_id: Identifier,
+        cursor: Identifier,
+        limit: i64,
+    ) -> Result<Vec<Message>, DatabaseError> {
+        let messages: Vec<Message> = tx
+            .get(
+                Document::Messages,
+                vec![
+                    SuperValue::Integer(user_id),
+                    SuperValue::Integer(other_id),
+                    SuperValue::Integer(cursor),
+                    SuperValue::BigInteger(limit),
+                ],
+                keywords!("tag" => String::from("messages")),
+            )
+            .await?;
+
+        Ok(messages)
+    }
+
+    pub async fn create_block(
+        &self,
+        tx: &mut Transaction,
+        user_id: Identifier,
+        blocked_id: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.set(
+            Document::Blocks,
+            vec![
+                SuperValue::Integer(user_id),
+                SuperValue::Integer(blocked_id),
+            ],
+            keywords!(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_block(
+        &self,
+        tx: &mut Transaction,
+        user_id: Identifier,
+        blocked_id: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.del(
+            Document::Blocks,
+            vec![
+                SuperValue::Integer(user_id),
+                SuperValue::Integer(blocked_id),
+            ],
+            keywords!(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn create_mute(
+        &self,
+        tx: &mut Transaction,
+        user_id: Identifier,
+        muted_id: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.set(
+            Document::Mutes,
+            vec![SuperValue::Integer(user_id), SuperValue::Integer(muted_id)],
+            keywords!(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_mute(
+        &self,
+        tx: &mut Transaction,
+        user_id: Identifier,
+        muted_id: Identifier,
+        backfill: Option<usize>,
+    ) -> Result<(), DatabaseError> {
+        let mut keywords = keywords!();
+        if let Some(limit) = backfill {
+            // Materialized timelines get the unmuted user's recent tweets back
+            keywords.insert("backfill", limit.to_string());
+        }
+        tx.del(
+            Document::Mutes,
+            vec![SuperValue::Integer(user_id), SuperValue::Integer(muted_id)],
+            keywords,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn create_bookmark(
+        &self,
+        tx: &mut Transaction,
+        user_id: Identifier,
+        tweet_id: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.set(
+            Document::Bookmarks,
+            vec![SuperValue::Integer(user_id), SuperValue::Integer(tweet_id)],
+            keywords!(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_bookmark(
+        &self,
+        tx: &mut Transaction,
+        user_id: Identifier,
+        tweet_id: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.del(
+            Document::Bookmarks,
+            vec![SuperValue::Integer(user_id), SuperValue::Integer(tweet_id)],
+            keywords!(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_bookmarks(
+        &self,
+        tx: &Transaction,
+        user_id: Identifier,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Tweet>, DatabaseError> {
+        let tweets: Vec<Tweet> = tx
+            .get(
+                Document::Bookmarks,
+                vec![
+                    SuperValue::Integer(user_id),
+                    SuperValue::BigInteger(limit),
+                    SuperValue::BigInteger(offset),
+                ],
+                keywords!("tag" => String::from("bookmarks")),
+            )
+            .await?;
+
+        Ok(tweets)
+    }
+
+    pub async fn create_list(
+        &self,
+        tx: &mut Transaction,
+        owner: Identifier,
+        list_name: String,
+    ) -> Result<(), DatabaseError> {
+        tx.set(
+            Document::Lists,
+            vec![SuperValue::Integer(owner), SuperValue::String(list_name)],
+            keywords!(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_list(
+        &self,
+        tx: &mut Transaction,
+        owner: Identifier,
+        list_name: String,
+    ) -> Result<(), DatabaseError> {
+        tx.del(
+            Document::Lists,
+            vec![SuperValue::Integer(owner), SuperValue::String(list_name)],
+            keywords!(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_lists(
+        &self,
+        tx: &Transaction,
+        owner: Identifier,
+    ) -> Result<Vec<List>, DatabaseError> {
+        let lists: Vec<List> = tx
+            .get(
+                Document::Lists,
+                vec![SuperValue::Integer(owner)],
+                keywords!("tag" => String::from("lists")),
+            )
+            .await?;
+
+        Ok(lists)
+    }
+
+    pub async fn create_list_member(
+        &self,
+        tx: &mut Transaction,
+        owner: Identifier,
+        list_name: String,
+        member: Identifier,
+        backfill: Option<usize>,
+    ) -> Result<(), DatabaseError> {
+        let mut keywords = keywords!("tag" => String::from("member"));
+        if let Some(limit) = backfill {
+            // Materialized list timelines get the new member's recent tweets
+            keywords.insert("backfill", limit.to_string());
+        }
+        tx.set(
+            Document::Lists,
+            vec![
+                SuperValue::Integer(owner),
+                SuperValue::String(list_name),
+                SuperValue::Integer(member),
+            ],
+            keywords,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_list_member(
+        &self,
+        tx: &mut Transaction,
+        owner: Identifier,
+        list_name: String,
+        member: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.del(
+            Document::Lists,
+            vec![
+                SuperValue::Integer(owner),
+                SuperValue::String(list_name),
+                SuperValue::Integer(member),
+            ],
+            keywords!("tag" => String::from("member")),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_list_timeline(
+        &self,
+        tx: &Transaction,
+        owner: Identifier,
+        list_name: String,
+    ) -> Result<Vec<Tweet>, DatabaseError> {
+        let tweets: Vec<Tweet> = tx
+            .get(
+                Document::Lists,
+                vec![
+                    SuperValue::Integer(owner),
+                    SuperValue::String(list_name),
+                    SuperValue::BigInteger(10),
+                    SuperValue::BigInteger(0),
+                ],
+                keywords!("tag" => String::from("list_timeline")),
+            )
+            .await?;
+
+        Ok(tweets)
+    }
+
+    pub async fn create_scheduled_tweet(
+        &self,
+        tx: &mut Transaction,
+        user_id: Identifier,
+        text: String,
+        publish_at: UnixTimestamp,
+    ) -> Result<(), DatabaseError> {
+        tx.set(
+            Document::ScheduledTweets,
+            vec![
+                SuperValue::Integer(user_id),
+                SuperValue::String(text),
+                SuperValue::Timestamp(publish_at),
+            ],
+            keywords!(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_scheduled_tweet(
+        &self,
+        tx: &mut Transaction,
+        user_id: Identifier,
+        scheduled_id: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.del(
+            Document::ScheduledTweets,
+            vec![
+                SuperValue::Integer(user_id),
+                SuperValue::Integer(scheduled_id),
+            ],
+            keywords!(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Removes a scheduled tweet claimed by `get_due_scheduled_tweets` right before it is
+    /// posted. Fails with `TxnKeyNotFound` once another scheduler took it
+    pub async fn take_scheduled_tweet(
+        &self,
+        tx: &mut Transaction,
+        user_id: Identifier,
+        scheduled_id: Identifier,
+    ) -> Result<(), DatabaseError> {
+        tx.del(
+            Document::ScheduledTweets,
+            vec![
+                SuperValue::Integer(user_id),
+                SuperValue::Integer(scheduled_id),
+            ],
+            keywords!("tag" => String::from("taken")),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_scheduled_tweets(
+        &self,
+        tx: &Transaction,
+        user_id: Identifier,
+    ) -> Result<Vec<ScheduledTweet>, DatabaseError> {
+        let tweets: Vec<ScheduledTweet> = tx
+            .get(
+                Document::ScheduledTweets,
+                vec![SuperValue::Integer(user_id)],
+                keywords!("tag" => String::from("scheduled")),
+            )
+            .await?;
+
+        Ok(tweets)
+    }
+
+    /// Claims the scheduled tweets due at `now` until they are taken, or on Postgres until
+    /// the transaction ends
+    pub async fn get_due_scheduled_tweets(
+        &self,
+        tx: &Transaction,
+        now: UnixTimestamp,
+        limit: i64,
+    ) -> Result<Vec<ScheduledTweet>, DatabaseError> {
+        let tweets: Vec<ScheduledTweet> = tx
+            .get(
+                Document::ScheduledTweets,
+                vec![SuperValue::Timestamp(now), SuperValue::BigInteger(limit)],
+                keywords!("tag" => String::from("due")),
+            )
+            .await?;
+
+        Ok(tweets)
+    }
 }
@@ -0,0 +1,27 @@
+use std::time::Duration;
+
+use log::{error, info};
+use tokio::{
+    task::JoinHandle,
+    time::{interval, MissedTickBehavior},
+};
+
+use crate::api::TwitterApi;
+
+/// Publishes the due scheduled tweets every `period` in the background. Scheduled tweets live
+/// in the database, so the ones that came due while no scheduler was running are published on
+/// the first tick
+pub fn spawn(api: TwitterApi, period: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = interval(period);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            match api.publish_due_tweets().await {
+                Ok(0) => {}
+                Ok(published) => info!("published {} scheduled tweets", published),
+                Err(err) => error!("failed to publish scheduled tweets: {:?}", err),
+            }
+        }
+    })
+}
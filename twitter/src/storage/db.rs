@@ -1,5 +1,7 @@
+use std::sync::Arc;
+
 use crate::errors::DatabaseError;
-use crate::structures::ImplDatabase;
+use crate::structures::{ImplDatabase, PoolConfig};
 
 #[cfg(feature = "rdb_postgres")]
 use super::PostgresAdapter;
@@ -7,13 +9,15 @@ use super::PostgresAdapter;
 use super::RedisAdapter;
 use super::Transaction;
 
+/// Cheaply cloneable handle to a connected database, shareable across tasks
+#[derive(Clone)]
 pub struct DatabaseRef {
-    pub db: Database,
+    pub db: Arc<Database>,
 }
 
 impl DatabaseRef {
     pub fn new(db: Database) -> Self {
-        DatabaseRef { db }
+        DatabaseRef { db: Arc::new(db) }
     }
 }
 
@@ -40,11 +44,14 @@ impl Database {
         name: DatabaseVariant,
         connection_str: &str,
         auto_reset: bool,
+        pool_config: PoolConfig,
     ) -> Database {
         match connection_str {
             #[cfg(feature = "rdb_postgres")]
             s if matches!(name, DatabaseVariant::Postgres) => {
-                let db = PostgresAdapter::connect(s, auto_reset).await.unwrap();
+                let db = PostgresAdapter::connect(s, auto_reset, &pool_config)
+                    .await
+                    .unwrap();
 
                 Database {
                     inner: Inner::Postgres(db),
@@ -52,7 +59,9 @@ impl Database {
             }
             #[cfg(feature = "kvs_redis")]
             s if matches!(name, DatabaseVariant::Redis) => {
-                let db = RedisAdapter::connect(s, auto_reset).await.unwrap();
+                let db = RedisAdapter::connect(s, auto_reset, &pool_config)
+                    .await
+                    .unwrap();
 
                 Database {
                     inner: Inner::Redis(db),
@@ -81,10 +90,10 @@ impl Database {
         )
     }
 
-    pub async fn transaction(&mut self, write: bool) -> Result<Transaction, DatabaseError> {
+    pub async fn transaction(&self, write: bool) -> Result<Transaction, DatabaseError> {
         macro_rules! impl_transaction_method {
 			($($x: ident feat $f: expr),*) => {
-				match &mut self.inner {
+				match &self.inner {
 					$(
 						#[cfg(feature = $f)]
 						Inner::$x(v) => {
@@ -0,0 +1,162 @@
+use std::collections::{HashMap, HashSet};
+
+use redis::{aio::Connection, AsyncCommands};
+
+use crate::{errors::DatabaseError, REDIS_STRATEGY};
+
+use super::{requests, timeline};
+
+/// Users blocked by the user
+fn blocks_key(user_id: &str) -> String {
+    format!("BLOCKS:{}", user_id)
+}
+
+/// Users blocking the user
+fn blocked_by_key(user_id: &str) -> String {
+    format!("BLOCKED_BY:{}", user_id)
+}
+
+/// Users muted by the user
+fn mutes_key(user_id: &str) -> String {
+    format!("MUTES:{}", user_id)
+}
+
+/// Users muting the user, excluded from the fan-out of their tweets
+pub(super) fn muted_by_key(user_id: &str) -> String {
+    format!("MUTED_BY:{}", user_id)
+}
+
+/// Fails with `TxConditionNotMet` when any of the flattened (a, b) pairs block each other
+pub(super) async fn reject_blocked(
+    conn: &mut Connection,
+    pairs: &[String],
+) -> Result<(), DatabaseError> {
+    let pipeline = &mut redis::pipe();
+    for pair in pairs.chunks(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        // Redis command: SISMEMBER BLOCKS:[a] [b]
+        pipeline.sismember(blocks_key(a), b);
+        // Redis command: SISMEMBER BLOCKS:[b] [a]
+        pipeline.sismember(blocks_key(b), a);
+    }
+    let blocked: Vec<bool> = pipeline.query_async(conn).await?;
+    if blocked.into_iter().any(|b| b) {
+        return Err(DatabaseError::TxConditionNotMet);
+    }
+    Ok(())
+}
+
+/// Args: user_id, blocked_id. Blocking drops the follows and requests in both directions
+pub(super) async fn block(conn: &mut Connection, args: &[String]) -> Result<(), DatabaseError> {
+    let (user_id, blocked_id) = (&args[0], &args[1]);
+    if user_id == blocked_id {
+        return Err(DatabaseError::TxConditionNotMet);
+    }
+    let pipeline = &mut redis::pipe();
+    // Redis command: SADD BLOCKS:[user_id] [blocked_id]
+    pipeline.sadd(blocks_key(user_id), blocked_id);
+    // Redis command: SADD BLOCKED_BY:[blocked_id] [user_id]
+    pipeline.sadd(blocked_by_key(blocked_id), user_id);
+    for (from, to) in [(user_id, blocked_id), (blocked_id, user_id)] {
+        // Redis command: SREM FOLLOWS:[from] [to]
+        pipeline.srem(format!("FOLLOWS:{}", from), to);
+        // Redis command: SREM FOLLOWED:[to] [from]
+        pipeline.srem(format!("FOLLOWED:{}", to), from);
+        // Redis command: ZREM FOLLOW_REQUESTS:[to] [from]
+        pipeline.zrem(requests::requests_key(to), from);
+        if REDIS_STRATEGY == 2 {
+            timeline::retract(conn, pipeline, from, to).await?;
+        }
+    }
+    pipeline.query_async::<_, ()>(conn).await?;
+    Ok(())
+}
+
+/// Args: user_id, blocked_id. Follows dropped by the block are not restored
+pub(super) async fn unblock(conn: &mut Connection, args: &[String]) -> Result<(), DatabaseError> {
+    let (user_id, blocked_id) = (&args[0], &args[1]);
+    let (removed, _): (i64, i64) = redis::pipe()
+        .srem(blocks_key(user_id), blocked_id)
+        .srem(blocked_by_key(blocked_id), user_id)
+        .query_async(conn)
+        .await?;
+    if removed == 0 {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    Ok(())
+}
+
+/// Args: user_id, muted_id. The follow is kept, only the home timeline is affected
+pub(super) async fn mute(conn: &mut Connection, args: &[String]) -> Result<(), DatabaseError> {
+    let (user_id, muted_id) = (&args[0], &args[1]);
+    if user_id == muted_id {
+        return Err(DatabaseError::TxConditionNotMet);
+    }
+    let pipeline = &mut redis::pipe();
+    // Redis command: SADD MUTES:[user_id] [muted_id]
+    pipeline.sadd(mutes_key(user_id), muted_id);
+    // Redis command: SADD MUTED_BY:[muted_id] [user_id]
+    pipeline.sadd(muted_by_key(muted_id), user_id);
+    if REDIS_STRATEGY == 2 {
+        // Retract the entries already materialized on the muter home timeline
+        timeline::retract(conn, pipeline, user_id, muted_id).await?;
+    }
+    pipeline.query_async::<_, ()>(conn).await?;
+    Ok(())
+}
+
+/// Args: user_id, muted_id. Materialized timelines get the `backfill` most recent tweets of
+/// the unmuted user back when it is still followed
+pub(super) async fn unmute(
+    conn: &mut Connection,
+    args: &[String],
+    backfill: Option<isize>,
+) -> Result<(), DatabaseError> {
+    let (user_id, muted_id) = (&args[0], &args[1]);
+    let (removed, _): (i64, i64) = redis::pipe()
+        .srem(mutes_key(user_id), muted_id)
+        .srem(muted_by_key(muted_id), user_id)
+        .query_async(conn)
+        .await?;
+    if removed == 0 {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    if let Some(limit) = backfill.filter(|_| REDIS_STRATEGY == 2) {
+        let following: bool = conn
+            .sismember(format!("FOLLOWS:{}", user_id), muted_id)
+            .await?;
+        if following {
+            timeline::backfill(conn, user_id, muted_id, limit).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Drops the timeline tweets written or retweeted by users the viewer mutes, blocks or is
+/// blocked by. Expects the JSON objects built by `timeline::read`
+pub(super) async fn filter_hidden(
+    conn: &mut Connection,
+    viewer: &str,
+    tweets: Vec<String>,
+) -> Result<Vec<String>, DatabaseError> {
+    // Redis command: SUNION MUTES:[viewer] BLOCKS:[viewer] BLOCKED_BY:[viewer]
+    let hidden: HashSet<String> = conn
+        .sunion(&[
+            mutes_key(viewer),
+            blocks_key(viewer),
+            blocked_by_key(viewer),
+        ])
+        .await?;
+    if hidden.is_empty() {
+        return Ok(tweets);
+    }
+    Ok(tweets
+        .into_iter()
+        .filter(|tweet| {
+            let fields: HashMap<String, String> = serde_json::from_str(tweet).unwrap();
+            !["user_id", "retweeted_by"]
+                .iter()
+                .any(|name| fields.get(*name).is_some_and(|id| hidden.contains(id)))
+        })
+        .collect())
+}
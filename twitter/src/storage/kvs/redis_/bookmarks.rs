@@ -0,0 +1,91 @@
+use chrono::Utc;
+use redis::{aio::Connection, AsyncCommands, Pipeline};
+
+use crate::errors::DatabaseError;
+
+use super::timeline::{self, Source};
+
+/// Tweets bookmarked by the user, scored by the bookmark timestamp
+fn bookmarks_key(user_id: &str) -> String {
+    format!("BOOKMARKS:{}", user_id)
+}
+
+/// Users who bookmarked the tweet, so its bookmarks can be dropped when it is deleted
+fn bookmarked_by_key(tweet_id: &str) -> String {
+    format!("BOOKMARKED_BY:{}", tweet_id)
+}
+
+/// Args: user_id, tweet_id
+pub(super) async fn bookmark(conn: &mut Connection, args: &[String]) -> Result<(), DatabaseError> {
+    let (user_id, tweet_id) = (&args[0], &args[1]);
+    let exists: bool = conn.exists(format!("TWEETS:{}", tweet_id)).await?;
+    if !exists {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    let timestamp = Utc::now().timestamp();
+    redis::pipe()
+        // Redis command: ZADD BOOKMARKS:[user_id] NX [timestamp] [tweet_id]
+        .cmd("ZADD")
+        .arg(bookmarks_key(user_id))
+        .arg("NX")
+        .arg(timestamp)
+        .arg(tweet_id)
+        .ignore()
+        // Redis command: SADD BOOKMARKED_BY:[tweet_id] [user_id]
+        .sadd(bookmarked_by_key(tweet_id), user_id)
+        .ignore()
+        .query_async::<_, ()>(conn)
+        .await?;
+    Ok(())
+}
+
+/// Args: user_id, tweet_id
+pub(super) async fn unbookmark(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<(), DatabaseError> {
+    let (user_id, tweet_id) = (&args[0], &args[1]);
+    let (removed, _): (i64, i64) = redis::pipe()
+        .zrem(bookmarks_key(user_id), tweet_id)
+        .srem(bookmarked_by_key(tweet_id), user_id)
+        .query_async(conn)
+        .await?;
+    if removed == 0 {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    Ok(())
+}
+
+/// Queues the removal of a deleted tweet from the bookmarks of every user
+pub(super) async fn remove(
+    conn: &mut Connection,
+    pipeline: &mut Pipeline,
+    tweet_id: &str,
+) -> Result<(), DatabaseError> {
+    let key = bookmarked_by_key(tweet_id);
+    let users: Vec<String> = conn.smembers(&key).await?;
+    for user_id in users {
+        // Redis command: ZREM BOOKMARKS:[user_id] [tweet_id]
+        pipeline.zrem(bookmarks_key(&user_id), tweet_id);
+    }
+    pipeline.del(key);
+    Ok(())
+}
+
+/// Args: user_id, limit, offset
+pub(super) async fn bookmarks(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Vec<String>, DatabaseError> {
+    let (user_id, limit, offset) = (&args[0], &args[1], &args[2]);
+    let (limit, offset) = (
+        limit.parse::<isize>().unwrap_or_default(),
+        offset.parse::<isize>().unwrap_or_default(),
+    );
+    if limit <= 0 {
+        return Ok(vec![]);
+    }
+    // Bookmarked tweets of protected users hide again after unfollowing
+    let key = bookmarks_key(user_id);
+    timeline::read_visible(conn, Source::Ranked(&key), user_id, limit, offset).await
+}
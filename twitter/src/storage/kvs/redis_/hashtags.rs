@@ -0,0 +1,135 @@
+use redis::{aio::Connection, AsyncCommands, Pipeline};
+
+use crate::{errors::DatabaseError, utils::parse_hashtags};
+
+use super::timeline::{self, Source};
+
+/// Width of the trending buckets in seconds
+const TRENDS_BUCKET: i64 = 60;
+/// Buckets older than a day expire, which bounds the trending window
+const TRENDS_RETENTION: i64 = 24 * 60 * 60;
+/// Merged windows are scratch keys, cleaned up after a bucket width
+const TRENDS_WINDOW_TTL: usize = TRENDS_BUCKET as usize;
+
+/// Tweets tagged with the hashtag, scored by their timestamp
+fn hashtag_key(tag: &str) -> String {
+    format!("HASHTAG:{}", tag)
+}
+
+/// Hashtag usage of the tweets posted within the bucket
+fn trends_key(bucket: i64) -> String {
+    format!("TRENDS:{}", bucket)
+}
+
+/// Queues the hashtag index and trending updates of a new tweet
+pub(super) fn add(pipeline: &mut Pipeline, tweet_id: i64, text: &str, timestamp: &str) {
+    let tags = parse_hashtags(text);
+    if tags.is_empty() {
+        return;
+    }
+    let bucket = trends_key(timestamp.parse::<i64>().unwrap_or_default() / TRENDS_BUCKET);
+    for tag in tags {
+        // Redis command: ZADD HASHTAG:[tag] [timestamp] [tweet_id]
+        pipeline.zadd(hashtag_key(&tag), tweet_id, timestamp);
+        // Redis command: ZINCRBY TRENDS:[bucket] 1 [tag]
+        pipeline.zincr(&bucket, &tag, 1);
+    }
+    pipeline.expire(&bucket, TRENDS_RETENTION as usize).ignore();
+}
+
+/// Decrements the hashtags (ARGV[2..]) of a trending bucket (KEYS[1]) and refreshes its
+/// retention (ARGV[1]). An expired bucket is left alone rather than recreated, and hashtags no
+/// longer used within the bucket are dropped
+const TRENDS_DECREMENT_SCRIPT: &str = r"
+if redis.call('EXISTS', KEYS[1]) == 0 then
+    return 0
+end
+for i = 2, #ARGV do
+    if redis.call('ZSCORE', KEYS[1], ARGV[i]) then
+        if tonumber(redis.call('ZINCRBY', KEYS[1], -1, ARGV[i])) <= 0 then
+            redis.call('ZREM', KEYS[1], ARGV[i])
+        end
+    end
+end
+if redis.call('EXISTS', KEYS[1]) == 1 then
+    redis.call('EXPIRE', KEYS[1], ARGV[1])
+end
+return 1
+";
+
+/// Queues the removal of a deleted tweet from the hashtag index and trending buckets
+pub(super) fn remove(pipeline: &mut Pipeline, tweet_id: &str, text: &str, timestamp: &str) {
+    let tags = parse_hashtags(text);
+    if tags.is_empty() {
+        return;
+    }
+    let bucket = trends_key(timestamp.parse::<i64>().unwrap_or_default() / TRENDS_BUCKET);
+    for tag in tags.iter() {
+        // Redis command: ZREM HASHTAG:[tag] [tweet_id]
+        pipeline.zrem(hashtag_key(tag), tweet_id);
+    }
+    // Redis command: EVAL [decrement] 1 TRENDS:[bucket] [retention] [tag ...]
+    pipeline
+        .cmd("EVAL")
+        .arg(TRENDS_DECREMENT_SCRIPT)
+        .arg(1)
+        .arg(&bucket)
+        .arg(TRENDS_RETENTION)
+        .arg(tags)
+        .ignore();
+}
+
+/// Args: tag, limit, offset, viewer
+pub(super) async fn hashtag_timeline(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Vec<String>, DatabaseError> {
+    let (tag, limit, offset) = (args[0].to_lowercase(), &args[1], &args[2]);
+    let (limit, offset) = (
+        limit.parse::<isize>().unwrap_or_default(),
+        offset.parse::<isize>().unwrap_or_default(),
+    );
+    if limit <= 0 {
+        return Ok(vec![]);
+    }
+    let key = hashtag_key(&tag);
+    timeline::read_visible(conn, Source::Ranked(&key), &args[3], limit, offset).await
+}
+
+/// Args: window in seconds, limit. Merges the buckets covering the window, returning
+/// `tag:count` for the most used hashtags
+pub(super) async fn trending(
+    conn: &mut Connection,
+    args: &[String],
+    now: i64,
+) -> Result<Vec<String>, DatabaseError> {
+    let window = args[0]
+        .parse::<i64>()
+        .unwrap_or_default()
+        .min(TRENDS_RETENTION);
+    let limit = args[1].parse::<isize>().unwrap_or_default();
+    if window <= 0 || limit <= 0 {
+        return Ok(vec![]);
+    }
+    let (first, last) = ((now - window) / TRENDS_BUCKET, now / TRENDS_BUCKET);
+    let window_key = format!("TRENDS:WINDOW:{}:{}", first, last);
+    let buckets = (first..=last).map(trends_key).collect::<Vec<String>>();
+
+    // Redis command: ZUNIONSTORE TRENDS:WINDOW:[first]:[last] [n] TRENDS:[bucket] ...
+    redis::pipe()
+        .zunionstore(&window_key, &buckets.iter().collect::<Vec<&String>>())
+        .ignore()
+        .expire(&window_key, TRENDS_WINDOW_TTL)
+        .ignore()
+        .query_async::<_, ()>(conn)
+        .await?;
+    // Redis command: ZREVRANGEBYSCORE TRENDS:WINDOW:[first]:[last] +inf 1 WITHSCORES LIMIT 0 [limit]
+    let trends: Vec<(String, i64)> = conn
+        .zrevrangebyscore_limit_withscores(&window_key, "+inf", 1, 0, limit)
+        .await?;
+
+    Ok(trends
+        .into_iter()
+        .map(|(tag, count)| format!("{}:{}", tag, count))
+        .collect())
+}
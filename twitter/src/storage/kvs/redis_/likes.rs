@@ -0,0 +1,81 @@
+use redis::{aio::Connection, AsyncCommands};
+
+use crate::errors::DatabaseError;
+
+pub(super) fn likes_key(tweet_id: &str) -> String {
+    format!("LIKES:{}", tweet_id)
+}
+
+pub(super) fn like_count_key(tweet_id: &str) -> String {
+    format!("LIKE_COUNT:{}", tweet_id)
+}
+
+/// Args: user_id, tweet_id
+pub(super) async fn like(conn: &mut Connection, args: &[String]) -> Result<(), DatabaseError> {
+    let (user_id, tweet_id) = (&args[0], &args[1]);
+    let exists: bool = conn.exists(format!("TWEETS:{}", tweet_id)).await?;
+    if !exists {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    // Redis command: SADD LIKES:[tweet_id] [user_id]
+    let added: i64 = conn.sadd(likes_key(tweet_id), user_id).await?;
+    // Liking twice is a no-op, so only count the first like
+    if added > 0 {
+        conn.incr::<_, _, ()>(like_count_key(tweet_id), 1).await?;
+    }
+    Ok(())
+}
+
+/// Args: user_id, tweet_id
+pub(super) async fn unlike(conn: &mut Connection, args: &[String]) -> Result<(), DatabaseError> {
+    let (user_id, tweet_id) = (&args[0], &args[1]);
+    // Redis command: SREM LIKES:[tweet_id] [user_id]
+    let removed: i64 = conn.srem(likes_key(tweet_id), user_id).await?;
+    if removed == 0 {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    conn.decr::<_, _, ()>(like_count_key(tweet_id), 1).await?;
+    Ok(())
+}
+
+/// Args: tweet_id
+pub(super) async fn like_count(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<String, DatabaseError> {
+    let count: Option<String> = conn.get(like_count_key(&args[0])).await?;
+    Ok(count.unwrap_or_else(|| "0".to_string()))
+}
+
+/// Args: tweet_id, limit, offset. Likers are ordered by user id like on Postgres
+pub(super) async fn liked_by(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Vec<String>, DatabaseError> {
+    let (tweet_id, limit, offset) = (&args[0], &args[1], &args[2]);
+    // Redis command: SORT LIKES:[tweet_id] LIMIT [offset] [limit]
+    let users: Vec<String> = redis::cmd("SORT")
+        .arg(likes_key(tweet_id))
+        .arg("LIMIT")
+        .arg(offset)
+        .arg(limit)
+        .query_async(conn)
+        .await?;
+    Ok(users)
+}
+
+/// Like count of each tweet, batched in a single MGET
+pub(super) async fn like_counts(
+    conn: &mut Connection,
+    tweet_ids: &[&str],
+) -> Result<Vec<i64>, DatabaseError> {
+    if tweet_ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let keys = tweet_ids
+        .iter()
+        .map(|id| like_count_key(id))
+        .collect::<Vec<String>>();
+    let counts: Vec<Option<i64>> = redis::cmd("MGET").arg(keys).query_async(conn).await?;
+    Ok(counts.into_iter().map(Option::unwrap_or_default).collect())
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use redis::{aio::Connection, AsyncCommands, Pipeline};
+
+use crate::{errors::DatabaseError, REDIS_STRATEGY};
+
+use super::{
+    blocks,
+    timeline::{self, Source},
+    users,
+};
+
+/// Lists of the owner, scored by their creation timestamp
+fn lists_key(owner: &str) -> String {
+    format!("LISTS:{}", owner)
+}
+
+fn members_key(owner: &str, name: &str) -> String {
+    format!("LIST_MEMBERS:{}:{}", owner, name)
+}
+
+/// Lists the user is a member of, formatted as `owner:name`
+fn listed_key(user_id: &str) -> String {
+    format!("LISTED:{}", user_id)
+}
+
+/// Materialized list timeline, newest first, used by strategy 2
+fn list_timeline_key(list: &str) -> String {
+    format!("LIST_TIMELINE:{}", list)
+}
+
+/// Args: owner, name
+pub(super) async fn create(conn: &mut Connection, args: &[String]) -> Result<(), DatabaseError> {
+    let (owner, name) = (&args[0], &args[1]);
+    // Redis command: ZADD LISTS:[owner] NX [timestamp] [name]
+    let added: i64 = redis::cmd("ZADD")
+        .arg(lists_key(owner))
+        .arg("NX")
+        .arg(Utc::now().timestamp())
+        .arg(name)
+        .query_async(conn)
+        .await?;
+    if added == 0 {
+        return Err(DatabaseError::TxKeyAlreadyExists);
+    }
+    Ok(())
+}
+
+/// Args: owner, name. Members and the list timeline go with the list
+pub(super) async fn delete(conn: &mut Connection, args: &[String]) -> Result<(), DatabaseError> {
+    let (owner, name) = (&args[0], &args[1]);
+    // Redis command: ZREM LISTS:[owner] [name]
+    let removed: i64 = conn.zrem(lists_key(owner), name).await?;
+    if removed == 0 {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    let list = format!("{}:{}", owner, name);
+    let members: Vec<String> = conn.smembers(members_key(owner, name)).await?;
+    let pipeline = &mut redis::pipe();
+    for member in members {
+        // Redis command: SREM LISTED:[member] [owner]:[name]
+        pipeline.srem(listed_key(&member), &list);
+    }
+    pipeline
+        .del(&[members_key(owner, name), list_timeline_key(&list)])
+        .query_async::<_, ()>(conn)
+        .await?;
+    Ok(())
+}
+
+/// Args: owner, name, member. Materialized list timelines get the `backfill` most recent
+/// tweets of the new member
+pub(super) async fn add_member(
+    conn: &mut Connection,
+    args: &[String],
+    backfill: Option<isize>,
+) -> Result<(), DatabaseError> {
+    let (owner, name, member) = (&args[0], &args[1], &args[2]);
+    // Redis command: ZSCORE LISTS:[owner] [name]
+    let created: Option<i64> = conn.zscore(lists_key(owner), name).await?;
+    if created.is_none() {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    let list = format!("{}:{}", owner, name);
+    let (added, _): (i64, i64) = redis::pipe()
+        .sadd(members_key(owner, name), member)
+        .sadd(listed_key(member), &list)
+        .query_async(conn)
+        .await?;
+    if let Some(limit) = backfill.filter(|_| added > 0 && REDIS_STRATEGY == 2) {
+        timeline::backfill_into(conn, &list_timeline_key(&list), member, limit).await?;
+    }
+    Ok(())
+}
+
+/// Args: owner, name, member
+pub(super) async fn remove_member(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<(), DatabaseError> {
+    let (owner, name, member) = (&args[0], &args[1], &args[2]);
+    let list = format!("{}:{}", owner, name);
+    let pipeline = &mut redis::pipe();
+    // Redis command: SREM LIST_MEMBERS:[owner]:[name] [member]
+    pipeline.srem(members_key(owner, name), member);
+    // Redis command: SREM LISTED:[member] [owner]:[name]
+    pipeline.srem(listed_key(member), &list);
+    if REDIS_STRATEGY == 2 {
+        timeline::retract_from(conn, pipeline, &list_timeline_key(&list), member).await?;
+    }
+    let removed: Vec<i64> = pipeline.query_async(conn).await?;
+    if removed[0] == 0 {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    Ok(())
+}
+
+/// Args: owner. Lists oldest first, as JSON objects of the list fields
+pub(super) async fn lists(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Vec<String>, DatabaseError> {
+    let owner = &args[0];
+    // Redis command: ZRANGE LISTS:[owner] 0 -1 WITHSCORES
+    let lists: Vec<(String, i64)> = conn.zrange_withscores(lists_key(owner), 0, -1).await?;
+    if lists.is_empty() {
+        return Ok(vec![]);
+    }
+    let pipeline = &mut redis::pipe();
+    for (name, _) in lists.iter() {
+        // Redis command: SCARD LIST_MEMBERS:[owner]:[name]
+        pipeline.scard(members_key(owner, name));
+    }
+    let counts: Vec<i64> = pipeline.query_async(conn).await?;
+
+    Ok(lists
+        .iter()
+        .zip(counts)
+        .map(|((name, created_ts), count)| {
+            let (count, created_ts) = (count.to_string(), created_ts.to_string());
+            let fields = HashMap::from([
+                ("owner_id", owner.as_str()),
+                ("list_name", name.as_str()),
+                ("member_count", count.as_str()),
+                ("created_ts", created_ts.as_str()),
+            ]);
+            serde_json::to_string(&fields).unwrap()
+        })
+        .collect())
+}
+
+/// Queues the tweet on the materialized timeline of every list the author is a member of
+pub(super) async fn fan_out(
+    conn: &mut Connection,
+    pipeline: &mut Pipeline,
+    author: &str,
+    content: &str,
+) -> Result<(), DatabaseError> {
+    let lists: Vec<String> = conn.smembers(listed_key(author)).await?;
+    for list in lists {
+        timeline::push(pipeline, &list_timeline_key(&list), content);
+    }
+    Ok(())
+}
+
+/// Queues the removal of a deleted tweet from the list timelines it was fanned out to
+pub(super) async fn retract(
+    conn: &mut Connection,
+    pipeline: &mut Pipeline,
+    author: &str,
+    content: &str,
+) -> Result<(), DatabaseError> {
+    let lists: Vec<String> = conn.smembers(listed_key(author)).await?;
+    for list in lists {
+        // Redis command: LREM LIST_TIMELINE:[owner]:[name] 0 [content]
+        pipeline.lrem(list_timeline_key(&list), 0, content);
+    }
+    Ok(())
+}
+
+/// Args: owner, name, limit, offset. Strategy 1 merges the members tweets on read, strategy 2
+/// reads the materialized list timeline
+pub(super) async fn list_timeline(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Vec<String>, DatabaseError> {
+    let (owner, name, limit, offset) = (&args[0], &args[1], &args[2], &args[3]);
+    let (limit, offset) = (
+        limit.parse::<isize>().unwrap_or_default(),
+        offset.parse::<isize>().unwrap_or_default(),
+    );
+    if limit <= 0 {
+        return Ok(vec![]);
+    }
+    if REDIS_STRATEGY == 1 {
+        let members: Vec<String> = conn.smembers(members_key(owner, name)).await?;
+        let mut contents = vec![];
+        for member in members {
+            let ids: Vec<String> = conn.lrange(format!("Users:{}", member), 0, -1).await?;
+            if ids.is_empty() {
+                continue;
+            }
+            let tweets: Vec<Option<String>> = redis::cmd("MGET").arg(ids).query_async(conn).await?;
+            contents.extend(tweets.into_iter().flatten());
+        }
+        // Newest first, like the materialized timelines
+        contents.sort_by_key(|t| std::cmp::Reverse(timeline::tweet_timestamp(t)));
+        let tweets = timeline::read(conn, contents).await?;
+        let tweets = blocks::filter_hidden(conn, owner, tweets).await?;
+        // Protected members only show up for the owner if it is an approved follower
+        let tweets = users::filter_protected(conn, owner, tweets).await?;
+        return Ok(tweets
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect());
+    }
+
+    // Protected members only show up for the owner if it is an approved follower
+    let key = list_timeline_key(&format!("{}:{}", owner, name));
+    timeline::read_visible(conn, Source::Timeline(&key), owner, limit, offset).await
+}
@@ -0,0 +1,88 @@
+use redis::{aio::Connection, AsyncCommands, Pipeline};
+
+use crate::{errors::DatabaseError, utils::parse_mentions};
+
+use super::{
+    timeline::{self, Source},
+    users::HANDLES,
+};
+
+/// Tweets mentioning the user, scored by their timestamp
+fn mentions_key(user_id: &str) -> String {
+    format!("MENTIONS:{}", user_id)
+}
+
+/// Users the tweet mentioned when it was posted, so its edges can be removed even after a
+/// mentioned user renamed their handle
+fn tweet_mentions_key(tweet_id: &str) -> String {
+    format!("TWEET_MENTIONS:{}", tweet_id)
+}
+
+/// Ids of the users mentioned in the tweet text, handles nobody owns are ignored
+async fn mentioned_users(conn: &mut Connection, text: &str) -> Result<Vec<String>, DatabaseError> {
+    let handles = parse_mentions(text);
+    if handles.is_empty() {
+        return Ok(vec![]);
+    }
+    // Redis command: HMGET HANDLES [handle ...]
+    let users: Vec<Option<String>> = redis::cmd("HMGET")
+        .arg(HANDLES)
+        .arg(handles)
+        .query_async(conn)
+        .await?;
+    Ok(users.into_iter().flatten().collect())
+}
+
+/// Queues a mention edge for every user mentioned in the tweet text
+pub(super) async fn add(
+    conn: &mut Connection,
+    pipeline: &mut Pipeline,
+    tweet_id: i64,
+    text: &str,
+    timestamp: &str,
+) -> Result<(), DatabaseError> {
+    let users = mentioned_users(conn, text).await?;
+    if users.is_empty() {
+        return Ok(());
+    }
+    for user_id in users.iter() {
+        // Redis command: ZADD MENTIONS:[user_id] [timestamp] [tweet_id]
+        pipeline.zadd(mentions_key(user_id), tweet_id, timestamp);
+    }
+    // Redis command: SADD TWEET_MENTIONS:[tweet_id] [user_id ...]
+    pipeline.sadd(tweet_mentions_key(&tweet_id.to_string()), users);
+    Ok(())
+}
+
+/// Queues the removal of the mention edges of a deleted tweet
+pub(super) async fn remove(
+    conn: &mut Connection,
+    pipeline: &mut Pipeline,
+    tweet_id: &str,
+) -> Result<(), DatabaseError> {
+    let key = tweet_mentions_key(tweet_id);
+    let users: Vec<String> = conn.smembers(&key).await?;
+    for user_id in users {
+        // Redis command: ZREM MENTIONS:[user_id] [tweet_id]
+        pipeline.zrem(mentions_key(&user_id), tweet_id);
+    }
+    pipeline.del(key);
+    Ok(())
+}
+
+/// Args: user_id, limit, offset
+pub(super) async fn mentions_timeline(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Vec<String>, DatabaseError> {
+    let (user_id, limit, offset) = (&args[0], &args[1], &args[2]);
+    let (limit, offset) = (
+        limit.parse::<isize>().unwrap_or_default(),
+        offset.parse::<isize>().unwrap_or_default(),
+    );
+    if limit <= 0 {
+        return Ok(vec![]);
+    }
+    let key = mentions_key(user_id);
+    timeline::read_visible(conn, Source::Ranked(&key), user_id, limit, offset).await
+}
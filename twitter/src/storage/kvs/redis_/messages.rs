@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use redis::{aio::Connection, AsyncCommands};
+
+use crate::errors::DatabaseError;
+
+use super::blocks;
+
+/// Counter the Redis message ids are allocated from
+const MESSAGE_ID_SEQ: &str = "MESSAGE_ID_SEQ";
+
+/// Messages between two users scored by their id, participants are in id order so a pair
+/// has a single conversation
+fn conversation_key(user_id: &str, other_id: &str) -> String {
+    let (a, b) = (
+        user_id.parse::<i64>().unwrap_or_default(),
+        other_id.parse::<i64>().unwrap_or_default(),
+    );
+    format!("DM:{}:{}", a.min(b), a.max(b))
+}
+
+/// Conversation peers of the user scored by the id of their last message
+fn inbox_key(user_id: &str) -> String {
+    format!("DM_INBOX:{}", user_id)
+}
+
+/// Splits a message entry into its id, sender, recipient, timestamp and text, the text may
+/// contain colons
+fn split_entry(entry: &str) -> (&str, &str, &str, &str, &str) {
+    let mut parts = entry.splitn(5, ':');
+    let mut next = || parts.next().unwrap_or_default();
+    (next(), next(), next(), next(), next())
+}
+
+/// Args: sender_id, recipient_id, text
+pub(super) async fn send(conn: &mut Connection, args: &[String]) -> Result<(), DatabaseError> {
+    let (sender, recipient, text) = (&args[0], &args[1], &args[2]);
+    if sender == recipient {
+        return Err(DatabaseError::TxConditionNotMet);
+    }
+    // Users who block each other can't message each other
+    blocks::reject_blocked(conn, &args[..2]).await?;
+    let timestamp = Utc::now().timestamp();
+    // Redis command: INCR MESSAGE_ID_SEQ
+    let message_id: i64 = conn.incr(MESSAGE_ID_SEQ, 1).await?;
+    let entry = format!(
+        "{}:{}:{}:{}:{}",
+        message_id, sender, recipient, timestamp, text
+    );
+    let pipeline = &mut redis::pipe();
+    // Redis command: ZADD DM:[a]:[b] [message_id] (id:sender:recipient:timestamp:text)
+    pipeline.zadd(conversation_key(sender, recipient), entry, message_id);
+    // Redis command: ZADD DM_INBOX:[user] [message_id] [peer], for both participants
+    pipeline.zadd(inbox_key(sender), recipient, message_id);
+    pipeline.zadd(inbox_key(recipient), sender, message_id);
+    pipeline.query_async::<_, ()>(conn).await?;
+    Ok(())
+}
+
+/// Args: user_id, limit. Each conversation is resolved with its last message
+pub(super) async fn conversations(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Vec<String>, DatabaseError> {
+    let (user_id, limit) = (&args[0], args[1].parse::<isize>().unwrap_or_default());
+    if limit <= 0 {
+        return Ok(vec![]);
+    }
+    // Redis command: ZREVRANGE DM_INBOX:[user_id] 0 [limit - 1]
+    let peers: Vec<String> = conn.zrevrange(inbox_key(user_id), 0, limit - 1).await?;
+    let pipeline = &mut redis::pipe();
+    for peer in peers.iter() {
+        // Redis command: ZREVRANGE DM:[a]:[b] 0 0
+        pipeline.zrevrange(conversation_key(user_id, peer), 0, 0);
+    }
+    let last_messages: Vec<Vec<String>> = pipeline.query_async(conn).await?;
+
+    let mut conversations = vec![];
+    for (peer, last) in peers.iter().zip(last_messages) {
+        let entry = match last.first() {
+            Some(entry) => entry,
+            None => continue,
+        };
+        let (_, sender, _, ts, text) = split_entry(entry);
+        let fields = HashMap::from([
+            ("user_id", peer.as_str()),
+            ("last_sender_id", sender),
+            ("last_message_text", text),
+            ("last_message_ts", ts),
+        ]);
+        conversations.push(serde_json::to_string(&fields).unwrap());
+    }
+    Ok(conversations)
+}
+
+/// Args: user_id, other_id, cursor, limit. Messages older than the cursor id, newest first
+pub(super) async fn messages(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Vec<String>, DatabaseError> {
+    let (user_id, other_id, cursor, limit) = (&args[0], &args[1], &args[2], &args[3]);
+    // Redis command: ZREVRANGEBYSCORE DM:[a]:[b] ([cursor] -inf LIMIT 0 [limit]
+    let entries: Vec<String> = conn
+        .zrevrangebyscore_limit(
+            conversation_key(user_id, other_id),
+            format!("({}", cursor),
+            "-inf",
+            0,
+            limit.parse::<isize>().unwrap_or_default(),
+        )
+        .await?;
+    Ok(entries
+        .iter()
+        .map(|entry| {
+            let (id, sender, recipient, ts, text) = split_entry(entry);
+            let fields = HashMap::from([
+                ("message_id", id),
+                ("sender_id", sender),
+                ("recipient_id", recipient),
+                ("message_text", text),
+                ("message_ts", ts),
+            ]);
+            serde_json::to_string(&fields).unwrap()
+        })
+        .collect())
+}
@@ -1,41 +1,63 @@
+mod blocks;
+mod bookmarks;
+mod hashtags;
+mod likes;
+mod lists;
+mod mentions;
+mod messages;
+mod quotes;
+mod requests;
+mod scheduled;
+mod search;
+mod threads;
+mod timeline;
 mod tx;
 mod ty;
+mod users;
 
-pub use tx::*;
 pub use ty::*;
 
 use async_trait::async_trait;
 
 use crate::{
     errors::DatabaseError,
-    structures::{DBTransaction, DatabaseAdapter, DatabaseType, ImplDatabase},
+    structures::{DBTransaction, DatabaseAdapter, DatabaseType, ImplDatabase, PoolConfig},
 };
-use redis::{Client, ConnectionLike};
+use bb8_redis::RedisConnectionManager;
 
 pub struct RedisAdapter(DatabaseAdapter<DBType>);
 
 impl RedisAdapter {
     impl_new_database!(DBType);
 
+    pub fn pool(&self) -> &DBType {
+        &self.0.db_instance
+    }
+
     pub async fn connect(
         connection_str: &str,
         auto_reset: bool,
+        pool_config: &PoolConfig,
     ) -> Result<RedisAdapter, DatabaseError> {
-        let mut client = Client::open(connection_str)?;
+        let manager = RedisConnectionManager::new(connection_str)?;
+        let pool = pool_config.builder().build(manager).await?;
 
-        if auto_reset && client.is_open() {
-            client.req_command(&redis::cmd("FLUSHALL"))?;
+        if auto_reset {
+            let mut conn = pool.get().await?;
+            redis::cmd("FLUSHALL")
+                .query_async::<_, ()>(&mut *conn)
+                .await?;
         }
 
         Ok(RedisAdapter(DatabaseAdapter::<DBType>::new(
             connection_str.to_string(),
-            Box::new(client),
+            pool,
             DatabaseType::KeyValueStore,
         )?))
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl ImplDatabase for RedisAdapter {
     type Transaction = RedisTransaction;
 
@@ -43,12 +65,9 @@ impl ImplDatabase for RedisAdapter {
         &self.0.connection_str
     }
 
-    async fn transaction(&mut self, w: bool) -> Result<RedisTransaction, DatabaseError> {
-        let connection = self
-            .get_mut_inner()
-            .db_instance
-            .get_async_connection()
-            .await?;
+    async fn transaction(&self, w: bool) -> Result<RedisTransaction, DatabaseError> {
+        // Every transaction leases its own connection from the pool
+        let connection = self.pool().get_owned().await?;
 
         Ok(DBTransaction::<TxType>::new(connection, w, false).unwrap())
     }
@@ -0,0 +1,60 @@
+use redis::{aio::Connection, AsyncCommands};
+
+use crate::errors::DatabaseError;
+
+use super::tx::post_tweets;
+
+/// Id of the tweet quoted by the tweet
+pub(super) fn quote_key(tweet_id: &str) -> String {
+    format!("QUOTE:{}", tweet_id)
+}
+
+/// Args: author, text, quoted_tweet_id
+pub(super) async fn quote(conn: &mut Connection, args: &[String]) -> Result<(), DatabaseError> {
+    let quoted_id = &args[2];
+    let exists: bool = conn.exists(format!("TWEETS:{}", quoted_id)).await?;
+    if !exists {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    let tweet_id = post_tweets(conn, &args[..2]).await?[0];
+    // Redis command: SET QUOTE:[id] [quoted_tweet_id]
+    conn.set::<_, _, ()>(quote_key(&tweet_id.to_string()), quoted_id)
+        .await?;
+    Ok(())
+}
+
+/// Quoted tweet id and content of each tweet, the content being `None` once the original
+/// is deleted. Both lookups are batched in a single MGET
+pub(super) async fn resolve(
+    conn: &mut Connection,
+    tweet_ids: &[&str],
+) -> Result<Vec<Option<(String, Option<String>)>>, DatabaseError> {
+    if tweet_ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let keys = tweet_ids
+        .iter()
+        .map(|id| quote_key(id))
+        .collect::<Vec<String>>();
+    let quoted_ids: Vec<Option<String>> = redis::cmd("MGET").arg(keys).query_async(conn).await?;
+
+    let quoted_keys = quoted_ids
+        .iter()
+        .flatten()
+        .map(|id| format!("TWEETS:{}", id))
+        .collect::<Vec<String>>();
+    let mut quoted: std::vec::IntoIter<Option<String>> = if quoted_keys.is_empty() {
+        vec![].into_iter()
+    } else {
+        let contents: Vec<Option<String>> = redis::cmd("MGET")
+            .arg(quoted_keys)
+            .query_async(conn)
+            .await?;
+        contents.into_iter()
+    };
+
+    Ok(quoted_ids
+        .into_iter()
+        .map(|id| id.map(|id| (id, quoted.next().flatten())))
+        .collect())
+}
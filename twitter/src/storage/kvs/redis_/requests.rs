@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use redis::{aio::Connection, AsyncCommands, Pipeline};
+
+use crate::{errors::DatabaseError, REDIS_STRATEGY};
+
+use super::timeline;
+
+/// Pending follow requests of a protected account, scored by their timestamp
+pub(super) fn requests_key(owner: &str) -> String {
+    format!("FOLLOW_REQUESTS:{}", owner)
+}
+
+/// Queues a follow request, requesting twice keeps the original timestamp
+pub(super) fn request(pipeline: &mut Pipeline, from: &str, to: &str, timestamp: i64) {
+    // Redis command: ZADD FOLLOW_REQUESTS:[to] NX [timestamp] [from]
+    pipeline
+        .cmd("ZADD")
+        .arg(requests_key(to))
+        .arg("NX")
+        .arg(timestamp)
+        .arg(from)
+        .ignore();
+}
+
+/// Turns accepted requests into follows, materialized timelines get the `backfill` most
+/// recent tweets of the owner
+async fn accept(
+    conn: &mut Connection,
+    owner: &str,
+    requesters: &[String],
+    backfill: Option<isize>,
+) -> Result<(), DatabaseError> {
+    if requesters.is_empty() {
+        return Ok(());
+    }
+    let pipeline = &mut redis::pipe();
+    for requester in requesters {
+        // Redis command: SADD FOLLOWS:[requester] [owner]
+        pipeline.sadd(format!("FOLLOWS:{}", requester), owner);
+        // Redis command: SADD FOLLOWED:[owner] [requester]
+        pipeline.sadd(format!("FOLLOWED:{}", owner), requester);
+    }
+    pipeline.query_async::<_, ()>(conn).await?;
+    if let Some(limit) = backfill.filter(|_| REDIS_STRATEGY == 2) {
+        for requester in requesters {
+            timeline::backfill(conn, requester, owner, limit).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Args: requester, owner
+pub(super) async fn approve(
+    conn: &mut Connection,
+    args: &[String],
+    backfill: Option<isize>,
+) -> Result<(), DatabaseError> {
+    let (requester, owner) = (&args[0], &args[1]);
+    // Redis command: ZREM FOLLOW_REQUESTS:[owner] [requester]
+    let removed: i64 = conn.zrem(requests_key(owner), requester).await?;
+    if removed == 0 {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    accept(conn, owner, &args[..1], backfill).await
+}
+
+/// Approves every pending request, once the account is no longer protected
+pub(super) async fn approve_all(
+    conn: &mut Connection,
+    owner: &str,
+    backfill: Option<isize>,
+) -> Result<(), DatabaseError> {
+    let key = requests_key(owner);
+    // Redis command: ZRANGE FOLLOW_REQUESTS:[owner] 0 -1
+    let requesters: Vec<String> = conn.zrange(&key, 0, -1).await?;
+    conn.del::<_, ()>(&key).await?;
+    accept(conn, owner, &requesters, backfill).await
+}
+
+/// Args: requester, owner
+pub(super) async fn reject(conn: &mut Connection, args: &[String]) -> Result<(), DatabaseError> {
+    let (requester, owner) = (&args[0], &args[1]);
+    // Redis command: ZREM FOLLOW_REQUESTS:[owner] [requester]
+    let removed: i64 = conn.zrem(requests_key(owner), requester).await?;
+    if removed == 0 {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    Ok(())
+}
+
+/// Args: owner. Pending requests oldest first, as JSON objects of the follow fields
+pub(super) async fn requests(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Vec<String>, DatabaseError> {
+    let owner = &args[0];
+    // Redis command: ZRANGE FOLLOW_REQUESTS:[owner] 0 -1 WITHSCORES
+    let requests: Vec<(String, i64)> = conn.zrange_withscores(requests_key(owner), 0, -1).await?;
+    Ok(requests
+        .into_iter()
+        .map(|(requester, timestamp)| {
+            let timestamp = timestamp.to_string();
+            let fields = HashMap::from([
+                ("from_id", requester.as_str()),
+                ("to_id", owner.as_str()),
+                ("follow_ts", timestamp.as_str()),
+                ("status", "pending"),
+            ]);
+            serde_json::to_string(&fields).unwrap()
+        })
+        .collect())
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use redis::{aio::Connection, AsyncCommands, Script};
+
+use crate::errors::DatabaseError;
+
+/// Counter the Redis scheduled tweet ids are allocated from
+const SCHEDULED_ID_SEQ: &str = "SCHEDULED_ID_SEQ";
+
+/// Every pending scheduled tweet, scored by its publication timestamp
+const SCHEDULED: &str = "SCHEDULED";
+
+/// Scheduled tweets claimed by a scheduler, scored by the claim timestamp. They leave right
+/// before being posted, so the ones of a scheduler that died meanwhile are claimed again
+const SCHEDULED_PROCESSING: &str = "SCHEDULED_PROCESSING";
+
+/// Seconds after which a claimed scheduled tweet that is still not taken is claimed again
+const SCHEDULED_CLAIM_TIMEOUT: i64 = 60;
+
+fn scheduled_key(scheduled_id: &str) -> String {
+    format!("SCHEDULED_TWEET:{}", scheduled_id)
+}
+
+/// Pending scheduled tweets of the user, scored by their publication timestamp
+fn scheduled_by_key(user_id: &str) -> String {
+    format!("SCHEDULED_BY:{}", user_id)
+}
+
+/// Reads the scheduled tweets as JSON objects of their fields, skipping the ones published or
+/// cancelled in the meantime
+async fn read(conn: &mut Connection, ids: Vec<String>) -> Result<Vec<String>, DatabaseError> {
+    if ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let pipeline = &mut redis::pipe();
+    for id in ids.iter() {
+        // Redis command: HGETALL SCHEDULED_TWEET:[id]
+        pipeline.hgetall(scheduled_key(id));
+    }
+    let tweets: Vec<HashMap<String, String>> = pipeline.query_async(conn).await?;
+    Ok(tweets
+        .into_iter()
+        .filter(|fields| !fields.is_empty())
+        .map(|fields| serde_json::to_string(&fields).unwrap())
+        .collect())
+}
+
+/// Args: user_id, text, publish_at
+pub(super) async fn schedule(conn: &mut Connection, args: &[String]) -> Result<(), DatabaseError> {
+    let (user_id, text, publish_at) = (&args[0], &args[1], &args[2]);
+    // Redis command: INCR SCHEDULED_ID_SEQ
+    let scheduled_id: i64 = conn.incr(SCHEDULED_ID_SEQ, 1).await?;
+    let publish_at = publish_at.parse::<i64>().unwrap_or_default();
+    let pipeline = &mut redis::pipe();
+    // Redis command: HSET SCHEDULED_TWEET:[id] (scheduled_id id) (user_id user_id) ...
+    pipeline.hset_multiple(
+        scheduled_key(&scheduled_id.to_string()),
+        &[
+            ("scheduled_id", scheduled_id.to_string()),
+            ("user_id", user_id.to_string()),
+            ("tweet_text", text.to_string()),
+            ("publish_at", publish_at.to_string()),
+        ],
+    );
+    // Redis command: ZADD SCHEDULED [publish_at] [id]
+    pipeline.zadd(SCHEDULED, scheduled_id, publish_at);
+    // Redis command: ZADD SCHEDULED_BY:[user_id] [publish_at] [id]
+    pipeline.zadd(scheduled_by_key(user_id), scheduled_id, publish_at);
+    pipeline.query_async::<_, ()>(conn).await?;
+    Ok(())
+}
+
+/// Removes a pending scheduled tweet (ARGV[1]) from SCHEDULED (KEYS[1]), then its hash (KEYS[2])
+/// and the author's pending tweets (KEYS[3]). Tweets already claimed by a scheduler are kept
+static CANCEL_SCRIPT: Lazy<Script> = Lazy::new(|| {
+    Script::new(
+        r"
+if redis.call('ZREM', KEYS[1], ARGV[1]) == 0 then
+    return 0
+end
+redis.call('DEL', KEYS[2])
+redis.call('ZREM', KEYS[3], ARGV[1])
+return 1
+",
+    )
+});
+
+/// Claims up to ARGV[2] scheduled tweets due at ARGV[1] by moving them from SCHEDULED (KEYS[1])
+/// to SCHEDULED_PROCESSING (KEYS[2]). Tweets claimed before ARGV[3] and still not published are
+/// claimed again first
+static CLAIM_SCRIPT: Lazy<Script> = Lazy::new(|| {
+    Script::new(
+        r"
+local claimed = redis.call('ZRANGEBYSCORE', KEYS[2], '-inf', ARGV[3], 'LIMIT', 0, ARGV[2])
+local left = tonumber(ARGV[2]) - #claimed
+if left > 0 then
+    for _, id in ipairs(redis.call('ZRANGEBYSCORE', KEYS[1], '-inf', ARGV[1], 'LIMIT', 0, left)) do
+        redis.call('ZREM', KEYS[1], id)
+        claimed[#claimed + 1] = id
+    end
+end
+for _, id in ipairs(claimed) do
+    redis.call('ZADD', KEYS[2], ARGV[1], id)
+end
+return claimed
+",
+    )
+});
+
+/// Args: user_id, scheduled_id. Only one of concurrent cancels of a tweet succeeds, and none
+/// once a scheduler claimed it for publishing
+pub(super) async fn cancel(conn: &mut Connection, args: &[String]) -> Result<(), DatabaseError> {
+    let (user_id, scheduled_id) = (&args[0], &args[1]);
+    let key = scheduled_key(scheduled_id);
+    // Only the author can cancel the scheduled tweet
+    // Redis command: HGET SCHEDULED_TWEET:[id] user_id
+    let author: Option<String> = conn.hget(&key, "user_id").await?;
+    if author.as_ref() != Some(user_id) {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    let removed: i64 = CANCEL_SCRIPT
+        .key(SCHEDULED)
+        .key(&key)
+        .key(scheduled_by_key(user_id))
+        .arg(scheduled_id)
+        .invoke_async(conn)
+        .await?;
+    if removed == 0 {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    Ok(())
+}
+
+/// Args: user_id, scheduled_id. Takes a claimed scheduled tweet out right before it is posted,
+/// only one of the schedulers that claimed it, e.g. again after a timeout, succeeds
+pub(super) async fn take(conn: &mut Connection, args: &[String]) -> Result<(), DatabaseError> {
+    let (user_id, scheduled_id) = (&args[0], &args[1]);
+    let (removed, _, _): (i64, i64, i64) = redis::pipe()
+        .atomic()
+        .zrem(SCHEDULED_PROCESSING, scheduled_id)
+        .zrem(scheduled_by_key(user_id), scheduled_id)
+        .del(scheduled_key(scheduled_id))
+        .query_async(conn)
+        .await?;
+    if removed == 0 {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    Ok(())
+}
+
+/// Args: user_id. Pending scheduled tweets, soonest first
+pub(super) async fn scheduled(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Vec<String>, DatabaseError> {
+    let user_id = &args[0];
+    // Redis command: ZRANGE SCHEDULED_BY:[user_id] 0 -1
+    let ids: Vec<String> = conn.zrange(scheduled_by_key(user_id), 0, -1).await?;
+    read(conn, ids).await
+}
+
+/// Args: now, limit. Claims the scheduled tweets due for publishing, oldest first. They stay
+/// claimed until `take`, or until the claim times out
+pub(super) async fn due(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Vec<String>, DatabaseError> {
+    let (now, limit) = (&args[0], &args[1]);
+    let limit = limit.parse::<isize>().unwrap_or_default();
+    if limit <= 0 {
+        return Ok(vec![]);
+    }
+    let stale = now.parse::<i64>().unwrap_or_default() - SCHEDULED_CLAIM_TIMEOUT;
+    let ids: Vec<String> = CLAIM_SCRIPT
+        .key(SCHEDULED)
+        .key(SCHEDULED_PROCESSING)
+        .arg(now)
+        .arg(limit)
+        .arg(stale)
+        .invoke_async(conn)
+        .await?;
+    read(conn, ids).await
+}
@@ -0,0 +1,80 @@
+use redis::{aio::Connection, Pipeline};
+
+use crate::{
+    errors::DatabaseError,
+    utils::{parse_search_query, tokenize},
+};
+
+use super::timeline::{self, Source};
+
+/// Merged query results are scratch keys, cleaned up after a minute
+const SEARCH_TTL: usize = 60;
+
+/// Inverted index of the term, tweets scored by their timestamp
+fn term_key(term: &str) -> String {
+    format!("TERM:{}", term)
+}
+
+/// Queues the inverted index updates of a new tweet
+pub(super) fn add(pipeline: &mut Pipeline, tweet_id: i64, text: &str, timestamp: &str) {
+    for term in tokenize(text) {
+        // Redis command: ZADD TERM:[term] [timestamp] [tweet_id]
+        pipeline.zadd(term_key(&term), tweet_id, timestamp);
+    }
+}
+
+/// Queues the removal of a deleted tweet from the inverted index
+pub(super) fn remove(pipeline: &mut Pipeline, tweet_id: &str, text: &str) {
+    for term in tokenize(text) {
+        pipeline.zrem(term_key(&term), tweet_id);
+    }
+}
+
+/// Args: query, limit, offset, viewer. Groups of terms are intersected then united, keeping the
+/// tweet timestamp as score so results are ordered by recency
+pub(super) async fn search(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Vec<String>, DatabaseError> {
+    let (limit, offset) = (
+        args[1].parse::<isize>().unwrap_or_default(),
+        args[2].parse::<isize>().unwrap_or_default(),
+    );
+    let groups = parse_search_query(&args[0]);
+    if groups.is_empty() || limit <= 0 {
+        return Ok(vec![]);
+    }
+
+    let pipeline = &mut redis::pipe();
+    let mut group_keys = vec![];
+    for group in groups.iter() {
+        if group.len() == 1 {
+            group_keys.push(term_key(&group[0]));
+            continue;
+        }
+        let group_key = format!("SEARCH:{}", group.join("&"));
+        let terms = group.iter().map(|t| term_key(t)).collect::<Vec<String>>();
+        // Redis command: ZINTERSTORE SEARCH:[terms] [n] TERM:[term] ... AGGREGATE MAX
+        pipeline
+            .zinterstore_max(&group_key, &terms.iter().collect::<Vec<&String>>())
+            .ignore()
+            .expire(&group_key, SEARCH_TTL)
+            .ignore();
+        group_keys.push(group_key);
+    }
+    let result_key = if group_keys.len() == 1 {
+        group_keys[0].clone()
+    } else {
+        let result_key = format!("SEARCH:{}", group_keys.join("|"));
+        // Redis command: ZUNIONSTORE SEARCH:[groups] [n] SEARCH:[group] ... AGGREGATE MAX
+        pipeline
+            .zunionstore_max(&result_key, &group_keys.iter().collect::<Vec<&String>>())
+            .ignore()
+            .expire(&result_key, SEARCH_TTL)
+            .ignore();
+        result_key
+    };
+    pipeline.query_async::<_, ()>(conn).await?;
+
+    timeline::read_visible(conn, Source::Ranked(&result_key), &args[3], limit, offset).await
+}
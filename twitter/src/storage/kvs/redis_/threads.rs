@@ -0,0 +1,131 @@
+use redis::{aio::Connection, AsyncCommands};
+
+use crate::errors::DatabaseError;
+
+use super::{timeline, tx::post_tweets, users};
+
+/// Replies of a tweet, in the order they were posted
+fn replies_key(tweet_id: &str) -> String {
+    format!("REPLIES:{}", tweet_id)
+}
+
+/// Thread of a reply, formatted as `in_reply_to:conversation_id`
+pub(super) fn thread_key(tweet_id: &str) -> String {
+    format!("THREAD:{}", tweet_id)
+}
+
+/// Conversation the tweet belongs to, a tweet outside of any thread starts its own
+async fn conversation_id(conn: &mut Connection, tweet_id: &str) -> Result<String, DatabaseError> {
+    let thread: Option<String> = conn.get(thread_key(tweet_id)).await?;
+    Ok(thread
+        .and_then(|t| t.split_once(':').map(|(_, c)| c.to_string()))
+        .unwrap_or_else(|| tweet_id.to_string()))
+}
+
+/// Args: author, text, in_reply_to
+pub(super) async fn reply(conn: &mut Connection, args: &[String]) -> Result<(), DatabaseError> {
+    let in_reply_to = &args[2];
+    let exists: bool = conn.exists(format!("TWEETS:{}", in_reply_to)).await?;
+    if !exists {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    let conversation = conversation_id(conn, in_reply_to).await?;
+    let tweet_id = post_tweets(conn, &args[..2]).await?[0].to_string();
+
+    redis::pipe()
+        // Redis command: SET THREAD:[id] [in_reply_to]:[conversation_id]
+        .set(
+            thread_key(&tweet_id),
+            format!("{}:{}", in_reply_to, conversation),
+        )
+        // Redis command: RPUSH REPLIES:[in_reply_to] [id]
+        .rpush(replies_key(in_reply_to), &tweet_id)
+        .query_async::<_, ()>(conn)
+        .await?;
+    Ok(())
+}
+
+/// Unlinks a deleted tweet from its thread. Its replies move up to its parent, in its place,
+/// so the rest of the thread stays reachable. Replies of a deleted conversation root become
+/// top level replies, still listed under the root
+pub(super) async fn unlink(conn: &mut Connection, tweet_id: &str) -> Result<(), DatabaseError> {
+    let thread: Option<String> = conn.get(thread_key(tweet_id)).await?;
+    let replies_id = replies_key(tweet_id);
+    let replies: Vec<String> = conn.lrange(&replies_id, 0, -1).await?;
+    let pipeline = &mut redis::pipe();
+    match thread.as_deref().and_then(|t| t.split_once(':')) {
+        Some((in_reply_to, conversation)) => {
+            // Top level replies of a deleted root are listed under the conversation
+            let parent = if in_reply_to.is_empty() {
+                conversation
+            } else {
+                in_reply_to
+            };
+            let siblings_id = replies_key(parent);
+            for reply in replies.iter() {
+                // Redis command: LINSERT REPLIES:[parent] BEFORE [id] [reply]
+                pipeline
+                    .cmd("LINSERT")
+                    .arg(&siblings_id)
+                    .arg("BEFORE")
+                    .arg(tweet_id)
+                    .arg(reply)
+                    .ignore();
+                // Redis command: SET THREAD:[reply] [in_reply_to]:[conversation_id]
+                pipeline
+                    .set(
+                        thread_key(reply),
+                        format!("{}:{}", in_reply_to, conversation),
+                    )
+                    .ignore();
+            }
+            pipeline.lrem(siblings_id, 0, tweet_id).ignore();
+            pipeline.del(&[thread_key(tweet_id), replies_id]).ignore();
+        }
+        None => {
+            // The replies of a conversation root keep the root as their conversation
+            for reply in replies.iter() {
+                // Redis command: SET THREAD:[reply] :[conversation_id]
+                pipeline
+                    .set(thread_key(reply), format!(":{}", tweet_id))
+                    .ignore();
+            }
+        }
+    }
+    pipeline.query_async::<_, ()>(conn).await?;
+    Ok(())
+}
+
+/// Args: tweet_id, max_depth, viewer. Walks the reply tree from the conversation root depth
+/// first. Once the root is deleted, its replies are the top level of the tree
+pub(super) async fn conversation(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Vec<String>, DatabaseError> {
+    let max_depth = args[1].parse::<i64>().unwrap_or_default();
+    let root = conversation_id(conn, &args[0]).await?;
+
+    let mut tweets = vec![];
+    let mut stack = vec![(root.clone(), 0)];
+    while let Some((tweet_id, depth)) = stack.pop() {
+        let content: Option<String> = conn.get(format!("TWEETS:{}", tweet_id)).await?;
+        let depth = match content {
+            Some(c) => {
+                tweets.push(c);
+                depth
+            }
+            // Replies of deleted tweets move up, only the root keeps its replies listed
+            None if tweet_id == root && depth == 0 => -1,
+            None => continue,
+        };
+        if depth < max_depth {
+            let replies: Vec<String> = conn.lrange(replies_key(&tweet_id), 0, -1).await?;
+            // Pushed in reverse so the earliest reply is visited first
+            stack.extend(replies.into_iter().rev().map(|r| (r, depth + 1)));
+        }
+    }
+
+    // Replies of protected tweets the viewer cannot see stay in the thread
+    let tweets = timeline::read(conn, tweets).await?;
+    users::filter_protected(conn, &args[2], tweets).await
+}
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use redis::{aio::Connection, AsyncCommands, Pipeline, Script};
+
+use crate::errors::DatabaseError;
+
+use super::{blocks, likes, quotes, threads::thread_key, users};
+
+/// Prefix of retweet entries, formatted as `RT:tweet_id:retweeter:timestamp`
+const RETWEET_PREFIX: &str = "RT:";
+
+/// Materialized timelines keep only their most recent entries, which bounds the cost of
+/// scanning one, e.g. to retract an unfollowed author
+pub(super) const TIMELINE_CAPACITY: isize = 800;
+
+pub(super) fn timeline_key(user_id: &str) -> String {
+    format!("USER_TIMELINE:{}", user_id)
+}
+
+pub(super) fn retweet_entry(tweet_id: &str, retweeter: &str, timestamp: &str) -> String {
+    format!("{}{}:{}:{}", RETWEET_PREFIX, tweet_id, retweeter, timestamp)
+}
+
+/// Queues an entry on top of a materialized timeline, dropping the entries past its capacity
+pub(super) fn push(pipeline: &mut Pipeline, timeline_id: &str, entry: &str) {
+    // Redis command: LPUSH [timeline] [entry]
+    pipeline.lpush(timeline_id, entry);
+    // Redis command: LTRIM [timeline] 0 [capacity - 1]
+    pipeline
+        .ltrim(timeline_id, 0, TIMELINE_CAPACITY - 1)
+        .ignore();
+}
+
+/// Tweet entries are formatted as `id:author:text:timestamp`
+pub(super) fn tweet_author(content: &str) -> Option<&str> {
+    content.split(':').nth(1)
+}
+
+/// User whose activity put the entry on the timeline, the retweeter for retweets
+pub(super) fn entry_owner(entry: &str) -> Option<&str> {
+    match entry.strip_prefix(RETWEET_PREFIX) {
+        Some(retweet) => retweet.split(':').nth(1),
+        None => tweet_author(entry),
+    }
+}
+
+/// Queues the removal of every entry the owner put on the user home timeline
+pub(super) async fn retract(
+    conn: &mut Connection,
+    pipeline: &mut Pipeline,
+    user_id: &str,
+    owner: &str,
+) -> Result<(), DatabaseError> {
+    retract_from(conn, pipeline, &timeline_key(user_id), owner).await
+}
+
+/// Queues the removal of every entry the owner put on a materialized timeline, e.g. a list
+/// timeline. Scans at most `TIMELINE_CAPACITY` entries
+pub(super) async fn retract_from(
+    conn: &mut Connection,
+    pipeline: &mut Pipeline,
+    timeline_id: &str,
+    owner: &str,
+) -> Result<(), DatabaseError> {
+    // Redis command: LRANGE [timeline] 0 [capacity - 1]
+    let entries: Vec<String> = conn.lrange(timeline_id, 0, TIMELINE_CAPACITY - 1).await?;
+    for entry in entries.iter().filter(|e| entry_owner(e) == Some(owner)) {
+        // Redis command: LREM [timeline] 0 [entry]
+        pipeline.lrem(timeline_id, 0, entry);
+    }
+    Ok(())
+}
+
+pub(super) fn tweet_timestamp(content: &str) -> i64 {
+    content
+        .rsplit(':')
+        .next()
+        .and_then(|ts| ts.parse::<i64>().ok())
+        .unwrap_or_default()
+}
+
+/// Merge the followee's `limit` most recent tweets into the follower home timeline.
+/// Only the first `limit` entries of the timeline are rewritten so the cost stays bounded.
+pub(super) async fn backfill(
+    conn: &mut Connection,
+    follower: &str,
+    followee: &str,
+    limit: isize,
+) -> Result<(), DatabaseError> {
+    backfill_into(conn, &timeline_key(follower), followee, limit).await
+}
+
+/// Merges backfilled entries (ARGV[3..]) into the first `limit` (ARGV[1]) entries of a timeline
+/// (KEYS[1]) in a single step, so entries fanned out meanwhile are never overwritten. Entries
+/// older than a full window would land above newer ones and are skipped. Ties keep the
+/// timeline entries first, then the backfilled ones in order
+static BACKFILL_SCRIPT: Lazy<Script> = Lazy::new(|| {
+    Script::new(
+        r"
+local limit, capacity = tonumber(ARGV[1]), tonumber(ARGV[2])
+local head = redis.call('LRANGE', KEYS[1], 0, limit - 1)
+local function timestamp(entry)
+    return tonumber(string.match(entry, ':(%d+)$')) or 0
+end
+local oldest = nil
+if #head == limit then
+    oldest = timestamp(head[#head])
+end
+local merged = {}
+for i, entry in ipairs(head) do
+    merged[#merged + 1] = {entry, timestamp(entry), i}
+end
+for i = 3, #ARGV do
+    local ts = timestamp(ARGV[i])
+    if oldest == nil or ts >= oldest then
+        merged[#merged + 1] = {ARGV[i], ts, #head + i}
+    end
+end
+if #merged == #head then
+    return 0
+end
+table.sort(merged, function(a, b)
+    if a[2] ~= b[2] then
+        return a[2] > b[2]
+    end
+    return a[3] < b[3]
+end)
+redis.call('LTRIM', KEYS[1], #head, -1)
+for i = #merged, 1, -1 do
+    redis.call('LPUSH', KEYS[1], merged[i][1])
+end
+redis.call('LTRIM', KEYS[1], 0, capacity - 1)
+return #merged - #head
+",
+    )
+});
+
+/// Merge the followee's `limit` most recent tweets into a materialized timeline, e.g. a
+/// list timeline
+pub(super) async fn backfill_into(
+    conn: &mut Connection,
+    timeline_id: &str,
+    followee: &str,
+    limit: isize,
+) -> Result<(), DatabaseError> {
+    if limit <= 0 {
+        return Ok(());
+    }
+    // Redis command: LRANGE Users:[followee] -limit -1
+    let ids: Vec<String> = conn
+        .lrange(format!("Users:{}", followee), -limit, -1)
+        .await?;
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let contents: Vec<Option<String>> = redis::cmd("MGET").arg(&ids).query_async(conn).await?;
+    let backfilled: Vec<String> = contents.into_iter().flatten().collect();
+    if backfilled.is_empty() {
+        return Ok(());
+    }
+
+    // Redis command: EVALSHA [backfill] 1 [timeline] [limit] [capacity] [entries...]
+    BACKFILL_SCRIPT
+        .key(timeline_id)
+        .arg(limit)
+        .arg(TIMELINE_CAPACITY)
+        .arg(backfilled)
+        .invoke_async::<_, i64>(conn)
+        .await?;
+
+    Ok(())
+}
+
+/// Splits tweet content into its id, author, text and timestamp, the text may contain colons
+pub(super) fn split_content(content: &str) -> (&str, &str, &str, &str) {
+    let mut head = content.splitn(3, ':');
+    let (id, author) = (head.next(), head.next());
+    let rest = head.next().unwrap_or_default();
+    let (text, ts) = rest.rsplit_once(':').unwrap_or((rest, ""));
+    (id.unwrap_or_default(), author.unwrap_or_default(), text, ts)
+}
+
+/// Resolves timeline entries into JSON objects of the tweet fields, with its like count,
+/// retweet, thread and quoted tweet. Retweets of deleted tweets are skipped
+pub(super) async fn read(
+    conn: &mut Connection,
+    entries: Vec<String>,
+) -> Result<Vec<String>, DatabaseError> {
+    // The retweeted tweets are batched in a single MGET, in the order of their entries
+    let retweet_keys = entries
+        .iter()
+        .filter_map(|entry| entry.strip_prefix(RETWEET_PREFIX))
+        .map(|retweet| format!("TWEETS:{}", retweet.split(':').next().unwrap_or_default()))
+        .collect::<Vec<String>>();
+    let mut originals: std::vec::IntoIter<Option<String>> = if retweet_keys.is_empty() {
+        vec![].into_iter()
+    } else {
+        let contents: Vec<Option<String>> = redis::cmd("MGET")
+            .arg(retweet_keys)
+            .query_async(conn)
+            .await?;
+        contents.into_iter()
+    };
+
+    let mut contents = vec![];
+    let mut retweets = vec![];
+    for entry in entries {
+        match entry.strip_prefix(RETWEET_PREFIX) {
+            Some(retweet) => {
+                let fields = retweet.splitn(3, ':').collect::<Vec<&str>>();
+                if let Some(content) = originals.next().flatten() {
+                    contents.push(content);
+                    retweets.push(Some((fields[1].to_string(), fields[2].to_string())));
+                }
+            }
+            None => {
+                contents.push(entry);
+                retweets.push(None);
+            }
+        }
+    }
+
+    let ids = contents
+        .iter()
+        .map(|c| split_content(c).0)
+        .collect::<Vec<&str>>();
+    let like_counts = likes::like_counts(conn, &ids).await?;
+    let threads = threads(conn, &ids).await?;
+    let quotes = quotes::resolve(conn, &ids).await?;
+
+    let mut tweets = vec![];
+    for (i, content) in contents.iter().enumerate() {
+        let (id, author, text, ts) = split_content(content);
+        let mut fields: HashMap<&str, String> = HashMap::from([
+            ("tweet_id", id.to_string()),
+            ("user_id", author.to_string()),
+            ("tweet_text", text.to_string()),
+            ("tweet_ts", ts.to_string()),
+            ("like_count", like_counts[i].to_string()),
+            // A tweet outside of any thread is its own conversation
+            ("conversation_id", id.to_string()),
+        ]);
+        if let Some((retweeted_by, retweet_ts)) = &retweets[i] {
+            fields.insert("retweeted_by", retweeted_by.to_string());
+            fields.insert("retweet_ts", retweet_ts.to_string());
+        }
+        if let Some((in_reply_to, conversation_id)) =
+            threads[i].as_deref().and_then(|t| t.split_once(':'))
+        {
+            fields.insert("in_reply_to", in_reply_to.to_string());
+            fields.insert("conversation_id", conversation_id.to_string());
+        }
+        if let Some((quoted_id, quoted)) = &quotes[i] {
+            fields.insert("quoted_tweet_id", quoted_id.to_string());
+            // The quoted tweet may have been deleted since
+            if let Some(quoted) = quoted {
+                let (_, author, text, ts) = split_content(quoted);
+                fields.insert("quoted_user_id", author.to_string());
+                fields.insert("quoted_tweet_text", text.to_string());
+                fields.insert("quoted_tweet_ts", ts.to_string());
+            }
+        }
+        tweets.push(serde_json::to_string(&fields).unwrap());
+    }
+    Ok(tweets)
+}
+
+/// Where `read_visible` reads a page of tweets from
+pub(super) enum Source<'a> {
+    /// Materialized timeline, newest entry first. Tweets of users the viewer mutes, blocks or
+    /// is blocked by are hidden too
+    Timeline(&'a str),
+    /// Sorted set of tweet ids, highest score first
+    Ranked(&'a str),
+}
+
+/// Reads a page of the tweets the viewer can see, as `read` does, skipping the `offset` first
+/// visible ones. Tweets of protected users the viewer is not an approved follower of are
+/// replaced by the next entries, so pages only come back short once the source runs out, like
+/// on Postgres where the visibility filter runs before LIMIT and OFFSET
+pub(super) async fn read_visible(
+    conn: &mut Connection,
+    source: Source<'_>,
+    viewer: &str,
+    limit: isize,
+    offset: isize,
+) -> Result<Vec<String>, DatabaseError> {
+    let mut tweets = vec![];
+    let mut skip = offset.max(0) as usize;
+    let mut start = 0;
+    while (tweets.len() as isize) < limit {
+        // Over-fetch, part of the entries may be hidden
+        let count = (limit - tweets.len() as isize + skip as isize) * 2;
+        let stop = start + count - 1;
+        let (page, exhausted) = match source {
+            Source::Timeline(key) => {
+                // Redis command: LRANGE [timeline] [start] [stop]
+                let entries: Vec<String> = conn.lrange(key, start, stop).await?;
+                let exhausted = (entries.len() as isize) < count;
+                let page = read(conn, entries).await?;
+                (blocks::filter_hidden(conn, viewer, page).await?, exhausted)
+            }
+            Source::Ranked(key) => {
+                // Redis command: ZREVRANGE [key] [start] [stop]
+                let ids: Vec<String> = conn.zrevrange(key, start, stop).await?;
+                let exhausted = (ids.len() as isize) < count;
+                (read_tweets(conn, ids).await?, exhausted)
+            }
+        };
+        let page = users::filter_protected(conn, viewer, page).await?;
+        let skipped = skip.min(page.len());
+        skip -= skipped;
+        tweets.extend(page.into_iter().skip(skipped));
+        if exhausted {
+            break;
+        }
+        start += count;
+    }
+    tweets.truncate(limit.max(0) as usize);
+    Ok(tweets)
+}
+
+/// Thread of each tweet as `in_reply_to:conversation_id`, batched in a single MGET
+async fn threads(
+    conn: &mut Connection,
+    tweet_ids: &[&str],
+) -> Result<Vec<Option<String>>, DatabaseError> {
+    if tweet_ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let keys = tweet_ids
+        .iter()
+        .map(|id| thread_key(id))
+        .collect::<Vec<String>>();
+    let threads: Vec<Option<String>> = redis::cmd("MGET").arg(keys).query_async(conn).await?;
+    Ok(threads)
+}
+
+/// Resolves tweet ids into tweets like `read`, skipping deleted tweets
+pub(super) async fn read_tweets(
+    conn: &mut Connection,
+    tweet_ids: Vec<String>,
+) -> Result<Vec<String>, DatabaseError> {
+    if tweet_ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let keys = tweet_ids
+        .iter()
+        .map(|id| format!("TWEETS:{}", id))
+        .collect::<Vec<String>>();
+    let contents: Vec<Option<String>> = redis::cmd("MGET").arg(keys).query_async(conn).await?;
+
+    read(conn, contents.into_iter().flatten().collect()).await
+}
@@ -12,9 +12,15 @@ use async_trait::async_trait;
 use chrono::{self, Utc};
 use rayon::prelude::*;
 use redis::{aio::Connection, AsyncCommands};
-use uuid::Uuid;
-
-use super::ty::TxType;
+use std::{cmp::Reverse, time::UNIX_EPOCH};
+
+use super::{
+    blocks, bookmarks, hashtags, likes, lists, mentions, messages, quotes, requests, scheduled,
+    search, threads,
+    timeline::{self, tweet_author},
+    ty::TxType,
+    users,
+};
 
 #[async_trait]
 impl SimpleTransaction for DBTransaction<TxType> {
@@ -50,7 +56,7 @@ impl SimpleTransaction for DBTransaction<TxType> {
         &mut self,
         key: K,
         args: A,
-        _keywords: KeywordBucket,
+        keywords: KeywordBucket,
     ) -> Result<(), DatabaseError>
     where
         K: Into<Key> + Send,
@@ -70,8 +76,7 @@ impl SimpleTransaction for DBTransaction<TxType> {
         let args = args.into();
 
         let params = to_redis_params(args);
-        key.execute_redis(conn, params.as_slice(), keywords!())
-            .await?;
+        key.execute_redis(conn, params.as_slice(), keywords).await?;
 
         Ok(())
     }
@@ -111,6 +116,44 @@ impl SimpleTransaction for DBTransaction<TxType> {
         Ok(())
     }
 
+    async fn bulk_set<K, A>(&mut self, key: K, args: Vec<A>) -> Result<(), DatabaseError>
+    where
+        K: Into<Key> + Send,
+        A: Into<Arg> + Send,
+    {
+        // Batches are already pipelined, there is no faster load path
+        self.multi_set(key, args).await
+    }
+
+    async fn del<K, A>(
+        &mut self,
+        key: K,
+        args: A,
+        keywords: KeywordBucket,
+    ) -> Result<(), DatabaseError>
+    where
+        K: Into<Key> + Send,
+        A: Into<Arg> + Send,
+    {
+        if self.closed() {
+            return Err(DatabaseError::TxFinished);
+        }
+
+        if !self.writable {
+            return Err(DatabaseError::TxReadonly);
+        }
+
+        let mut guarded_tx = self.tx.lock().await;
+        let conn = guarded_tx.as_mut().unwrap();
+        let key: Key = key.into();
+        let args = args.into();
+
+        let params = to_redis_params(args);
+        key.delete_redis(conn, params.as_slice(), keywords).await?;
+
+        Ok(())
+    }
+
     async fn get<K, A, V>(
         &self,
         key: K,
@@ -139,34 +182,88 @@ impl SimpleTransaction for DBTransaction<TxType> {
     }
 }
 
-type RedisReturnType = Box<String>;
+type RedisReturnType = String;
 fn to_redis_params(params: Vec<SuperValue>) -> Vec<RedisReturnType> {
     let mut result: Vec<RedisReturnType> = vec![];
 
     for item in params {
+        // Timestamps are passed as seconds since the epoch, like the scores they are stored as
+        if let SuperValue::Timestamp(v) = item {
+            let secs = v.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+            result.push(secs.to_string());
+            continue;
+        }
         macro_rules! param_convert {
             ($($SuperValueType: ident),*) => {
                 match item {
                     $(
-                        SuperValue::$SuperValueType(v) => result.push(
-                            Box::new(v.to_string())
-                        ),
+                        SuperValue::$SuperValueType(v) => result.push(v.to_string()),
                     )*
                     _ => unimplemented!()
                 }
             };
         }
-        param_convert!(String, Integer, BigInteger, SmallInteger, Char);
+        param_convert!(String, Integer, BigInteger, SmallInteger, Char, Bool);
     }
 
     result
 }
 
+/// Counter the Redis tweet ids are allocated from
+const TWEET_ID_SEQ: &str = "TWEET_ID_SEQ";
+
+fn tweet_key(tweet_id: i64) -> String {
+    format!("TWEETS:{}", tweet_id)
+}
+
+/// Posts flattened (author, text) pairs and returns the allocated tweet ids
+pub(super) async fn post_tweets(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Vec<i64>, DatabaseError> {
+    /* Redis command: SET TWEETS:id (id:author:text:timestamp)
+    The id is allocated from a counter so every entry carries a stable tweet id */
+    let timestamp = Utc::now().timestamp().to_string();
+    // Single posts and batches are both flattened (author, text) pairs
+    let count = (args.len() / 2) as i64;
+    // Redis command: INCRBY TWEET_ID_SEQ [count]
+    let last_id: i64 = conn.incr(TWEET_ID_SEQ, count).await?;
+    let mut pipeline = &mut redis::pipe();
+    for (tweet_id, tweet) in (last_id - count + 1..).zip(args.chunks(2)) {
+        let id = &tweet_key(tweet_id);
+        let author_id = &tweet[0];
+        // Add tweets to list owned by the tweet author
+        let user_id = &format!("Users:{}", author_id);
+        let content = &format!("{}:{}:{}:{}", tweet_id, author_id, tweet[1], timestamp);
+
+        pipeline = pipeline.set(id, content).rpush(user_id, id);
+        mentions::add(conn, pipeline, tweet_id, &tweet[1], &timestamp).await?;
+        hashtags::add(pipeline, tweet_id, &tweet[1], &timestamp);
+        search::add(pipeline, tweet_id, &tweet[1], &timestamp);
+        if REDIS_STRATEGY == 2 {
+            // Add tweet to follower home timeline, unless they muted the author
+            let followed_id = format!("FOLLOWED:{}", author_id);
+            let followers: Vec<String> = conn
+                .sdiff(&[followed_id, blocks::muted_by_key(author_id)])
+                .await?;
+            for follower in followers {
+                let timeline_id = format!("USER_TIMELINE:{}", follower);
+                // Don't need to use sorted list as this is pushed in order
+                timeline::push(pipeline, &timeline_id, content);
+            }
+            lists::fan_out(conn, pipeline, author_id, content).await?;
+        }
+    }
+    pipeline.query_async::<_, ()>(conn).await?;
+
+    Ok((last_id - count + 1..=last_id).collect())
+}
+
 impl Document {
     pub async fn query_redis<T>(
-        self: &Self,
+        &self,
         conn: &mut Connection,
-        args: &[Box<String>],
+        args: &[String],
         keywords: KeywordBucket,
     ) -> Result<Vec<T>, DatabaseError>
     where
@@ -174,39 +271,72 @@ impl Document {
     {
         let tag = keywords.get("tag").unwrap();
         match self {
+            Document::Tweets if tag == "conversation" => {
+                let tweets = threads::conversation(conn, args).await?;
+                return Ok(tweets.into_iter().map(T::from_redis_value).collect());
+            }
+            Document::Tweets if tag == "mentions_timeline" => {
+                let tweets = mentions::mentions_timeline(conn, args).await?;
+                return Ok(tweets.into_iter().map(T::from_redis_value).collect());
+            }
+            Document::Tweets if tag == "hashtag_timeline" => {
+                let tweets = hashtags::hashtag_timeline(conn, args).await?;
+                return Ok(tweets.into_iter().map(T::from_redis_value).collect());
+            }
+            Document::Tweets if tag == "search" => {
+                let tweets = search::search(conn, args).await?;
+                return Ok(tweets.into_iter().map(T::from_redis_value).collect());
+            }
+            Document::Hashtags if tag == "trending" => {
+                let trends = hashtags::trending(conn, args, Utc::now().timestamp()).await?;
+                return Ok(trends.into_iter().map(T::from_redis_value).collect());
+            }
             Document::Tweets => {
                 if tag == "user_timeline" {
                     if REDIS_STRATEGY == 1 {
+                        let limit = args[1].parse::<usize>().unwrap_or_default();
                         let id = format!("FOLLOWS:{}", &args[0]);
-                        let followees: Vec<String> = conn.lrange(id, 0, -1).await?;
-                        let mut tweets_ids: Vec<String> = vec![];
+                        let followees: Vec<String> = conn.smembers(id).await?;
+                        let mut contents: Vec<String> = vec![];
                         for followee in followees.into_iter() {
-                            let followee_tweets_ids: Vec<String> = conn
-                                .lrange(format!("USERS:{}", followee).to_string(), 0, -1)
-                                .await?;
-                            for id in followee_tweets_ids {
-                                let tweet: String = conn.get(id).await?;
-                                tweets_ids.push(tweet);
+                            // Redis command: LRANGE Users:[followee] 0 -1
+                            let followee_tweets_ids: Vec<String> =
+                                conn.lrange(format!("Users:{}", followee), 0, -1).await?;
+                            if followee_tweets_ids.is_empty() {
+                                continue;
                             }
+                            let tweets: Vec<Option<String>> = redis::cmd("MGET")
+                                .arg(followee_tweets_ids)
+                                .query_async(conn)
+                                .await?;
+                            contents.extend(tweets.into_iter().flatten());
                         }
 
-                        // Parallel mapping tweet timestamp into integer
-                        let mut timestamps: Vec<_> = tweets_ids
-                            .par_iter()
-                            .map(|k| k.clone()[k.len() - 10..k.len()].parse::<i64>().unwrap())
-                            .collect();
-                        // Can do parallel sorting (thread safe)
-                        timestamps.par_sort();
+                        // Newest first, like the materialized timelines. Can do parallel
+                        // sorting (thread safe)
+                        contents.par_sort_by_key(|t| Reverse(timeline::tweet_timestamp(t)));
 
+                        let tweets = timeline::read(conn, contents).await?;
+                        let tweets = blocks::filter_hidden(conn, &args[0], tweets).await?;
+                        let tweets = users::filter_protected(conn, &args[0], tweets).await?;
                         let mut result = vec![];
-                        for tweet in tweets_ids {
+                        for tweet in tweets.into_iter().take(limit) {
                             result.push(T::from_redis_value(tweet));
                         }
 
                         return Ok(result);
                     } else if REDIS_STRATEGY == 2 {
-                        let timeline = format!("USER_TIMELINE:{}", &args[0]);
-                        let tweets: Vec<String> = conn.lrange(timeline, 0, 10).await?;
+                        let limit = args[1].parse::<isize>().unwrap_or_default();
+                        // Retweets of hidden users' tweets are not retracted when muting
+                        let key = timeline::timeline_key(&args[0]);
+                        let tweets = timeline::read_visible(
+                            conn,
+                            timeline::Source::Timeline(&key),
+                            &args[0],
+                            limit,
+                            0,
+                        )
+                        .await?;
                         let mut result = vec![];
                         for tweet in tweets {
                             result.push(T::from_redis_value(tweet));
@@ -215,6 +345,72 @@ impl Document {
                     }
                 }
             }
+            Document::Users => {
+                if tag == "user" {
+                    if let Some(user) = users::get_user(conn, args).await? {
+                        return Ok(vec![T::from_redis_value(user)]);
+                    }
+                } else if tag == "user_by_handle" {
+                    if let Some(user) = users::get_user_by_handle(conn, args).await? {
+                        return Ok(vec![T::from_redis_value(user)]);
+                    }
+                } else if tag == "autocomplete" {
+                    let users = users::autocomplete(conn, args).await?;
+                    return Ok(users.into_iter().map(T::from_redis_value).collect());
+                }
+            }
+            Document::Likes => match tag.as_str() {
+                "like_count" => {
+                    let count = likes::like_count(conn, args).await?;
+                    return Ok(vec![T::from_redis_value(count)]);
+                }
+                "liked_by" => {
+                    let users = likes::liked_by(conn, args).await?;
+                    return Ok(users.into_iter().map(T::from_redis_value).collect());
+                }
+                _ => {}
+            },
+            Document::Lists => match tag.as_str() {
+                "lists" => {
+                    let lists = lists::lists(conn, args).await?;
+                    return Ok(lists.into_iter().map(T::from_redis_value).collect());
+                }
+                "list_timeline" => {
+                    let tweets = lists::list_timeline(conn, args).await?;
+                    return Ok(tweets.into_iter().map(T::from_redis_value).collect());
+                }
+                _ => {}
+            },
+            Document::ScheduledTweets => match tag.as_str() {
+                "scheduled" => {
+                    let tweets = scheduled::scheduled(conn, args).await?;
+                    return Ok(tweets.into_iter().map(T::from_redis_value).collect());
+                }
+                "due" => {
+                    let tweets = scheduled::due(conn, args).await?;
+                    return Ok(tweets.into_iter().map(T::from_redis_value).collect());
+                }
+                _ => {}
+            },
+            Document::Bookmarks if tag == "bookmarks" => {
+                let tweets = bookmarks::bookmarks(conn, args).await?;
+                return Ok(tweets.into_iter().map(T::from_redis_value).collect());
+            }
+            Document::Follows if tag == "requests" => {
+                let requests = requests::requests(conn, args).await?;
+                return Ok(requests.into_iter().map(T::from_redis_value).collect());
+            }
+            Document::Messages => match tag.as_str() {
+                "conversations" => {
+                    let conversations = messages::conversations(conn, args).await?;
+                    return Ok(conversations.into_iter().map(T::from_redis_value).collect());
+                }
+                "messages" => {
+                    let messages = messages::messages(conn, args).await?;
+                    return Ok(messages.into_iter().map(T::from_redis_value).collect());
+                }
+                _ => {}
+            },
             _ => unimplemented!(),
         };
 
@@ -222,70 +418,210 @@ impl Document {
     }
 
     pub async fn execute_redis(
-        self: &Self,
+        &self,
         conn: &mut Connection,
-        args: &[Box<String>],
+        args: &[String],
         keywords: KeywordBucket,
     ) -> Result<(), DatabaseError> {
         match self {
-            Document::Tweets => {
-                /* Redis command: HSET Tweets:author_id (id uuidV4)
-                (tweet_ts timestamp) (tweet_text msg) (author id) */
-                let uuidv4 = Uuid::new_v4();
-                // (id uuidV4)
-                let timestamp = Utc::now().timestamp().to_string();
-                // Add tweets to set owned by the tweet author
-                let is_batch = keywords.get("is_batch");
-                let user_id = &format!("Users:{}", args[0]).to_string();
-                match is_batch {
-                    Some(_) => {
-                        // IF REDIS_STRATEGY is defined
-                        if REDIS_STRATEGY > 0 {
-                            let mut pipeline = &mut redis::pipe();
-                            let mut batch_id = 0;
-                            while batch_id < args.len() {
-                                let id = &format!("TWEETS:{}", uuidv4);
-                                let author_id = &args[batch_id];
-                                let content = &format!(
-                                    "{}:{}:{}:{}",
-                                    uuidv4,
-                                    author_id,
-                                    args[batch_id + 1],
-                                    timestamp
-                                );
-
-                                pipeline = pipeline.set(id, content).rpush(user_id, id);
-                                if REDIS_STRATEGY == 2 {
-                                    // Add tweet to follower home timeline
-                                    let followed_id = format!("FOLLOWED:{}", author_id);
-                                    let followers: Vec<String> =
-                                        conn.lrange(followed_id, 0, -1).await?;
-                                    for follower in followers {
-                                        let timeline_id = format!("USER_TIMELINE:{}", follower);
-                                        // Don't need to use sorted list as this is pushed in order
-                                        pipeline.lpush(timeline_id, content);
-                                    }
-                                }
-                                batch_id += 2;
-                            }
-                            pipeline.query_async(conn).await?;
+            Document::Tweets => match keywords.get("tag").as_deref() {
+                Some("reply") => threads::reply(conn, args).await?,
+                Some("quote") => quotes::quote(conn, args).await?,
+                _ => {
+                    post_tweets(conn, args).await?;
+                }
+            },
+            Document::Follows if keywords.get("tag").as_deref() == Some("approve") => {
+                let backfill = keywords
+                    .get("backfill")
+                    .map(|limit| limit.parse::<isize>().unwrap_or_default());
+                requests::approve(conn, args, backfill).await?
+            }
+            Document::Follows => {
+                blocks::reject_blocked(conn, args).await?;
+                let targets = args.chunks(2).map(|f| f[1].as_str()).collect::<Vec<_>>();
+                let protected = users::protected(conn, &targets).await?;
+                let timestamp = Utc::now().timestamp();
+                // Initialize follows relationship using sets so following twice is a no-op
+                let pipeline = &mut redis::pipe();
+                let mut follows = vec![];
+                // Batches are flattened (from, to) pairs
+                for (follow, protected) in args.chunks(2).zip(protected) {
+                    let (from, to) = (&follow[0], &follow[1]);
+                    let follow_id = format!("FOLLOWS:{}", from);
+                    if protected {
+                        // Protected accounts approve their followers, unless already approved
+                        let following: bool = conn.sismember(&follow_id, to).await?;
+                        if !following {
+                            requests::request(pipeline, from, to, timestamp);
+                        }
+                        continue;
+                    }
+                    // Redis command: SADD FOLLOWS:[from] [to]
+                    pipeline.sadd(&follow_id, to.to_string());
+                    // Redis command: SADD FOLLOWED:[to] [from]
+                    let followed_id = format!("FOLLOWED:{}", to);
+                    pipeline.sadd(&followed_id, from.to_string());
+                    follows.push(follow);
+                }
+                // Number of members added by each SADD, zero when already following
+                let added: Vec<i64> = pipeline.query_async(conn).await?;
+                if let Some(limit) = keywords.get("backfill") {
+                    let limit = limit.parse::<isize>().unwrap_or_default();
+                    for (follow, added) in follows.into_iter().zip(added.chunks(2)) {
+                        if REDIS_STRATEGY == 2 && added[0] > 0 {
+                            timeline::backfill(conn, &follow[0], &follow[1], limit).await?;
                         }
                     }
-                    None => {
-                        let id = &format!("TWEETS:{}", uuidv4);
-                        conn.rpush(user_id, id).await?;
+                }
+            }
+            Document::Users => match keywords.get("tag").as_deref() {
+                Some("profile") => users::update_user(conn, args).await?,
+                Some("protected") => {
+                    let backfill = keywords
+                        .get("backfill")
+                        .map(|limit| limit.parse::<isize>().unwrap_or_default());
+                    users::set_protected(conn, args, backfill).await?
+                }
+                _ => users::create_user(conn, args).await?,
+            },
+            Document::Likes => likes::like(conn, args).await?,
+            Document::Messages => messages::send(conn, args).await?,
+            Document::Bookmarks => bookmarks::bookmark(conn, args).await?,
+            Document::Lists => match keywords.get("tag").as_deref() {
+                Some("member") => {
+                    let backfill = keywords
+                        .get("backfill")
+                        .map(|limit| limit.parse::<isize>().unwrap_or_default());
+                    lists::add_member(conn, args, backfill).await?
+                }
+                _ => lists::create(conn, args).await?,
+            },
+            Document::ScheduledTweets => scheduled::schedule(conn, args).await?,
+            Document::Blocks => blocks::block(conn, args).await?,
+            Document::Mutes => blocks::mute(conn, args).await?,
+            Document::Retweets => {
+                let (user_id, tweet_id) = (&args[0], &args[1]);
+                let content: Option<String> = conn.get(format!("TWEETS:{}", tweet_id)).await?;
+                let author = match content.as_deref().and_then(tweet_author) {
+                    Some(author) => author.to_string(),
+                    None => return Err(DatabaseError::TxnKeyNotFound),
+                };
+                // Protected tweets would otherwise reach the retweeter's followers
+                if users::protected(conn, &[&author]).await?[0] {
+                    return Err(DatabaseError::TxConditionNotMet);
+                }
+                // Redis command: SADD RETWEETS:[tweet_id] [user_id]
+                let added: i64 = conn.sadd(format!("RETWEETS:{}", tweet_id), user_id).await?;
+                // Retweeting twice is a no-op
+                if added > 0 && REDIS_STRATEGY == 2 {
+                    let timestamp = Utc::now().timestamp().to_string();
+                    let entry = timeline::retweet_entry(tweet_id, user_id, &timestamp);
+                    // Add a reference to the original tweet to the retweeter followers home timeline
+                    let followers: Vec<String> = conn
+                        .sdiff(&[
+                            format!("FOLLOWED:{}", user_id),
+                            blocks::muted_by_key(user_id),
+                        ])
+                        .await?;
+                    let pipeline = &mut redis::pipe();
+                    for follower in followers {
+                        timeline::push(pipeline, &timeline::timeline_key(&follower), &entry);
                     }
+                    pipeline.query_async::<_, ()>(conn).await?;
                 }
             }
+            _ => unimplemented!(),
+        };
+        Ok(())
+    }
+
+    pub async fn delete_redis(
+        &self,
+        conn: &mut Connection,
+        args: &[String],
+        keywords: KeywordBucket,
+    ) -> Result<(), DatabaseError> {
+        match self {
+            Document::Tweets => {
+                let (tweet_id, author_id) = (&args[0], &args[1]);
+                let id = &format!("TWEETS:{}", tweet_id);
+                let content: Option<String> = conn.get(id).await?;
+                // Only the author can delete the tweet
+                let content = match content {
+                    Some(c) if tweet_author(&c) == Some(author_id) => c,
+                    _ => return Err(DatabaseError::TxnKeyNotFound),
+                };
+                let pipeline = &mut redis::pipe();
+                // Redis command: DEL TWEETS:[id]
+                pipeline.del(id);
+                // Redis command: LREM Users:[author] 0 TWEETS:[id]
+                pipeline.lrem(format!("Users:{}", author_id), 0, id);
+                // Redis command: DEL LIKES:[id] LIKE_COUNT:[id] RETWEETS:[id]
+                pipeline.del(&[
+                    likes::likes_key(tweet_id),
+                    likes::like_count_key(tweet_id),
+                    format!("RETWEETS:{}", tweet_id),
+                    quotes::quote_key(tweet_id),
+                ]);
+                let (_, _, text, timestamp) = timeline::split_content(&content);
+                mentions::remove(conn, pipeline, tweet_id).await?;
+                hashtags::remove(pipeline, tweet_id, text, timestamp);
+                search::remove(pipeline, tweet_id, text);
+                bookmarks::remove(conn, pipeline, tweet_id).await?;
+                threads::unlink(conn, tweet_id).await?;
+                if REDIS_STRATEGY == 2 {
+                    // Retract the tweet from every follower home timeline
+                    let followers: Vec<String> =
+                        conn.smembers(format!("FOLLOWED:{}", author_id)).await?;
+                    for follower in followers {
+                        pipeline.lrem(format!("USER_TIMELINE:{}", follower), 0, &content);
+                    }
+                    lists::retract(conn, pipeline, author_id, &content).await?;
+                }
+                pipeline.query_async::<_, ()>(conn).await?;
+            }
+            Document::Follows if keywords.get("tag").as_deref() == Some("request") => {
+                requests::reject(conn, args).await?
+            }
             Document::Follows => {
-                // Initialize follows relationship using lists data structure
                 let (from, to) = (&args[0], &args[1]);
-                // Redis command: LPUSH [from]:Follows [to]
-                let follow_id = format!("FOLLOWS:{}", from);
-                conn.lpush(&follow_id, to.to_string()).await?;
-                // Redis command: LPUSH Followed:[to] [from]
-                let followed_id = format!("FOLLOWED:{}", to);
-                conn.lpush(&followed_id, from.to_string()).await?;
+                let pipeline = &mut redis::pipe();
+                // Redis command: SREM FOLLOWS:[from] [to]
+                pipeline.srem(format!("FOLLOWS:{}", from), to);
+                // Redis command: SREM FOLLOWED:[to] [from]
+                pipeline.srem(format!("FOLLOWED:{}", to), from);
+                // Unfollowing a protected account also withdraws a pending request
+                // Redis command: ZREM FOLLOW_REQUESTS:[to] [from]
+                pipeline.zrem(requests::requests_key(to), from);
+                if REDIS_STRATEGY == 2 {
+                    // Retract the unfollowed author's tweets and retweets from the follower
+                    // home timeline
+                    timeline::retract(conn, pipeline, from, to).await?;
+                }
+                // Number of members removed by each command, zero when neither following nor
+                // requested
+                let removed: Vec<i64> = pipeline.query_async(conn).await?;
+                if removed[0] == 0 && removed[2] == 0 {
+                    return Err(DatabaseError::TxnKeyNotFound);
+                }
+            }
+            Document::Likes => likes::unlike(conn, args).await?,
+            Document::Bookmarks => bookmarks::unbookmark(conn, args).await?,
+            Document::Lists => match keywords.get("tag").as_deref() {
+                Some("member") => lists::remove_member(conn, args).await?,
+                _ => lists::delete(conn, args).await?,
+            },
+            Document::ScheduledTweets => match keywords.get("tag").as_deref() {
+                Some("taken") => scheduled::take(conn, args).await?,
+                _ => scheduled::cancel(conn, args).await?,
+            },
+            Document::Blocks => blocks::unblock(conn, args).await?,
+            Document::Mutes => {
+                let backfill = keywords
+                    .get("backfill")
+                    .map(|limit| limit.parse::<isize>().unwrap_or_default());
+                blocks::unmute(conn, args, backfill).await?
             }
             _ => unimplemented!(),
         };
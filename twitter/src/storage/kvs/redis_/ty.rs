@@ -1,9 +1,10 @@
 extern crate redis;
 
-use redis::{aio::Connection, Client};
+use bb8::{Pool, PooledConnection};
+use bb8_redis::RedisConnectionManager;
 
 use crate::structures::DBTransaction;
 
-pub type TxType = Connection;
-pub type DBType = Box<Client>;
+pub type TxType = PooledConnection<'static, RedisConnectionManager>;
+pub type DBType = Pool<RedisConnectionManager>;
 pub type RedisTransaction = DBTransaction<TxType>;
@@ -0,0 +1,314 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use redis::{aio::Connection, AsyncCommands, Script};
+
+use crate::errors::DatabaseError;
+
+use super::requests;
+
+/// Hash of lowercased handles to the user owning them
+pub(super) const HANDLES: &str = "HANDLES";
+/// Lowercased handles in lexicographic order, all scored 0, for prefix lookups
+const HANDLES_LEX: &str = "HANDLES_LEX";
+/// Handles matching a prefix that are ranked by follower count, at least, like on Postgres
+const AUTOCOMPLETE_CANDIDATES: usize = 50;
+
+/// Longest profile fields, in characters, like the Users columns on Postgres
+const MAX_HANDLE_LENGTH: usize = 15;
+const MAX_DISPLAY_NAME_LENGTH: usize = 50;
+const MAX_BIO_LENGTH: usize = 160;
+
+/// Creates the profile (KEYS[1]) of a user (ARGV[1]) unless it exists or its lowercased handle
+/// (ARGV[3]) is taken in HANDLES (KEYS[2]), so concurrent creates can't both succeed. The handle
+/// is added to HANDLES_LEX (KEYS[3]). ARGV[2], ARGV[4..6] are the handle, display name, bio and
+/// creation timestamp
+static CREATE_USER_SCRIPT: Lazy<Script> = Lazy::new(|| {
+    Script::new(
+        r"
+if redis.call('EXISTS', KEYS[1]) == 1 then
+    return 0
+end
+if redis.call('HSETNX', KEYS[2], ARGV[3], ARGV[1]) == 0 then
+    return 0
+end
+redis.call('ZADD', KEYS[3], 0, ARGV[3])
+redis.call('HSET', KEYS[1], 'user_id', ARGV[1], 'handle', ARGV[2], 'display_name', ARGV[4],
+    'bio', ARGV[5], 'created_ts', ARGV[6])
+return 1
+",
+    )
+});
+
+fn profile_key(user_id: &str) -> String {
+    format!("PROFILES:{}", user_id)
+}
+
+async fn claim_handle(
+    conn: &mut Connection,
+    handle: &str,
+    user_id: &str,
+) -> Result<(), DatabaseError> {
+    // Redis command: HSETNX HANDLES [handle] [user_id]
+    let claimed: bool = conn
+        .hset_nx(HANDLES, handle.to_lowercase(), user_id)
+        .await?;
+    if !claimed {
+        return Err(DatabaseError::TxKeyAlreadyExists);
+    }
+    // Redis command: ZADD HANDLES_LEX 0 [handle]
+    conn.zadd::<_, _, _, ()>(HANDLES_LEX, handle.to_lowercase(), 0)
+        .await?;
+    Ok(())
+}
+
+/// Profile fields longer than the Users columns allow are rejected, like on Postgres
+fn check_profile(handle: &str, display_name: &str, bio: &str) -> Result<(), DatabaseError> {
+    if handle.chars().count() > MAX_HANDLE_LENGTH
+        || display_name.chars().count() > MAX_DISPLAY_NAME_LENGTH
+        || bio.chars().count() > MAX_BIO_LENGTH
+    {
+        return Err(DatabaseError::TxConditionNotMet);
+    }
+    Ok(())
+}
+
+/// Args: user_id, handle, display_name, bio
+pub(super) async fn create_user(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<(), DatabaseError> {
+    let (user_id, handle, display_name, bio) = (&args[0], &args[1], &args[2], &args[3]);
+    check_profile(handle, display_name, bio)?;
+    let created: i64 = CREATE_USER_SCRIPT
+        .key(profile_key(user_id))
+        .key(HANDLES)
+        .key(HANDLES_LEX)
+        .arg(user_id)
+        .arg(handle)
+        .arg(handle.to_lowercase())
+        .arg(display_name)
+        .arg(bio)
+        .arg(Utc::now().timestamp())
+        .invoke_async(conn)
+        .await?;
+    if created == 0 {
+        return Err(DatabaseError::TxKeyAlreadyExists);
+    }
+    Ok(())
+}
+
+/// Args: user_id, handle, display_name, bio
+pub(super) async fn update_user(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<(), DatabaseError> {
+    let (user_id, handle, display_name, bio) = (&args[0], &args[1], &args[2], &args[3]);
+    check_profile(handle, display_name, bio)?;
+    let profile = profile_key(user_id);
+    let current: Option<String> = conn.hget(&profile, "handle").await?;
+    let current = current.ok_or(DatabaseError::TxnKeyNotFound)?;
+
+    if current.to_lowercase() != handle.to_lowercase() {
+        claim_handle(conn, handle, user_id).await?;
+        redis::pipe()
+            .hdel(HANDLES, current.to_lowercase())
+            .zrem(HANDLES_LEX, current.to_lowercase())
+            .query_async::<_, ()>(conn)
+            .await?;
+    }
+    conn.hset_multiple::<_, _, _, ()>(
+        &profile,
+        &[
+            ("handle", handle),
+            ("display_name", display_name),
+            ("bio", bio),
+        ],
+    )
+    .await?;
+    Ok(())
+}
+
+/// Args: user_id, protected. Pending requests are approved once the account is public
+pub(super) async fn set_protected(
+    conn: &mut Connection,
+    args: &[String],
+    backfill: Option<isize>,
+) -> Result<(), DatabaseError> {
+    let (user_id, protected) = (&args[0], &args[1]);
+    let profile = profile_key(user_id);
+    let exists: bool = conn.exists(&profile).await?;
+    if !exists {
+        return Err(DatabaseError::TxnKeyNotFound);
+    }
+    // Redis command: HSET PROFILES:[user_id] protected [protected]
+    conn.hset::<_, _, _, ()>(&profile, "protected", protected)
+        .await?;
+    if protected != "true" {
+        requests::approve_all(conn, user_id, backfill).await?;
+    }
+    Ok(())
+}
+
+/// Whether each user has a protected account, batched in a single pipeline
+pub(super) async fn protected(
+    conn: &mut Connection,
+    user_ids: &[&str],
+) -> Result<Vec<bool>, DatabaseError> {
+    let pipeline = &mut redis::pipe();
+    for user_id in user_ids {
+        // Redis command: HGET PROFILES:[user_id] protected
+        pipeline.hget(profile_key(user_id), "protected");
+    }
+    let protected: Vec<Option<String>> = pipeline.query_async(conn).await?;
+    Ok(protected
+        .into_iter()
+        .map(|p| p.as_deref() == Some("true"))
+        .collect())
+}
+
+/// Protected users among the authors the viewer is not an approved follower of
+async fn hidden_authors<'a>(
+    conn: &mut Connection,
+    viewer: &str,
+    authors: impl Iterator<Item = &'a str>,
+) -> Result<HashSet<&'a str>, DatabaseError> {
+    let unique = authors
+        .filter(|author| !author.is_empty() && *author != viewer)
+        .collect::<HashSet<&str>>()
+        .into_iter()
+        .collect::<Vec<&str>>();
+    let mut hidden = HashSet::new();
+    if unique.is_empty() {
+        return Ok(hidden);
+    }
+    let protected = protected(conn, &unique).await?;
+    for (author, protected) in unique.into_iter().zip(protected) {
+        // Redis command: SISMEMBER FOLLOWED:[author] [viewer]
+        if protected
+            && !conn
+                .sismember(format!("FOLLOWED:{}", author), viewer)
+                .await?
+        {
+            hidden.insert(author);
+        }
+    }
+    Ok(hidden)
+}
+
+/// Drops the tweets of protected users the viewer is not an approved follower of, and the
+/// embeds of their quoted tweets. Expects the JSON objects built by `timeline::read`
+pub(super) async fn filter_protected(
+    conn: &mut Connection,
+    viewer: &str,
+    tweets: Vec<String>,
+) -> Result<Vec<String>, DatabaseError> {
+    let mut parsed = tweets
+        .iter()
+        .map(|tweet| serde_json::from_str(tweet).unwrap())
+        .collect::<Vec<HashMap<String, String>>>();
+    let authors = parsed
+        .iter()
+        .flat_map(|fields| ["user_id", "quoted_user_id"].map(|name| fields.get(name)))
+        .flatten()
+        .cloned()
+        .collect::<Vec<String>>();
+    let hidden = hidden_authors(conn, viewer, authors.iter().map(String::as_str)).await?;
+    if hidden.is_empty() {
+        return Ok(tweets);
+    }
+    let is_hidden = |fields: &HashMap<String, String>, name: &str| {
+        fields
+            .get(name)
+            .is_some_and(|id| hidden.contains(id.as_str()))
+    };
+    parsed.retain(|fields| !is_hidden(fields, "user_id"));
+    for fields in parsed.iter_mut() {
+        // The quoted tweet stays referenced, like a deleted one
+        if is_hidden(fields, "quoted_user_id") {
+            for name in ["quoted_user_id", "quoted_tweet_text", "quoted_tweet_ts"] {
+                fields.remove(name);
+            }
+        }
+    }
+    Ok(parsed
+        .iter()
+        .map(|fields| serde_json::to_string(fields).unwrap())
+        .collect())
+}
+
+/// Returns the profile hash, with its follow counters, serialized as JSON
+pub(super) async fn get_user(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Option<String>, DatabaseError> {
+    let user_id = &args[0];
+    let mut fields: HashMap<String, String> = conn.hgetall(profile_key(user_id)).await?;
+    if fields.is_empty() {
+        return Ok(None);
+    }
+
+    let followers_count: i64 = conn.scard(format!("FOLLOWED:{}", user_id)).await?;
+    let following_count: i64 = conn.scard(format!("FOLLOWS:{}", user_id)).await?;
+    fields.insert("followers_count".to_string(), followers_count.to_string());
+    fields.insert("following_count".to_string(), following_count.to_string());
+
+    Ok(Some(serde_json::to_string(&fields).unwrap()))
+}
+
+/// Args: handle. Resolves the case insensitive handle through the HANDLES index
+pub(super) async fn get_user_by_handle(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Option<String>, DatabaseError> {
+    // Redis command: HGET HANDLES [handle]
+    let user_id: Option<String> = conn.hget(HANDLES, args[0].to_lowercase()).await?;
+    match user_id {
+        Some(user_id) => get_user(conn, &[user_id]).await,
+        None => Ok(None),
+    }
+}
+
+/// Args: prefix, limit. Takes the first handles starting with the prefix in lexicographic
+/// order, at least `limit` of them, and ranks them by follower count
+pub(super) async fn autocomplete(
+    conn: &mut Connection,
+    args: &[String],
+) -> Result<Vec<String>, DatabaseError> {
+    let prefix = args[0].to_lowercase();
+    let limit = args[1].parse::<usize>().unwrap_or_default();
+    let min = format!("[{}", prefix);
+    // 0xFF never appears in UTF-8, so it closes the prefix range
+    let mut max = min.clone().into_bytes();
+    max.push(0xff);
+    let candidates = AUTOCOMPLETE_CANDIDATES.max(limit) as isize;
+    // Redis command: ZRANGEBYLEX HANDLES_LEX [prefix [prefix\xff LIMIT 0 [candidates]
+    let handles: Vec<String> = conn
+        .zrangebylex_limit(HANDLES_LEX, min, max, 0, candidates)
+        .await?;
+
+    let mut users = vec![];
+    for handle in handles {
+        let user_id: Option<String> = conn.hget(HANDLES, &handle).await?;
+        if let Some(user) = match user_id {
+            Some(user_id) => get_user(conn, &[user_id]).await?,
+            None => None,
+        } {
+            let fields: HashMap<String, String> = serde_json::from_str(&user).unwrap();
+            let followers = fields
+                .get("followers_count")
+                .and_then(|c| c.parse::<i64>().ok())
+                .unwrap_or_default();
+            users.push((followers, user));
+        }
+    }
+    // Stable sort keeps the lexicographic order between users with as many followers
+    users.sort_by_key(|(followers, _)| std::cmp::Reverse(*followers));
+
+    Ok(users
+        .into_iter()
+        .take(limit)
+        .map(|(_, user)| user)
+        .collect())
+}
@@ -0,0 +1,91 @@
+use std::{
+    collections::HashMap,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use async_trait::async_trait;
+use bb8::ManageConnection;
+use bb8_postgres::PostgresConnectionManager;
+use tokio_postgres::{Client, Error, NoTls, Statement};
+
+/// ## CachedClient
+/// Postgres client that keeps the statements it prepared, so scripts which
+/// are generated at runtime (e.g. batch inserts of a given size) are only
+/// parsed once per connection.
+pub struct CachedClient {
+    client: Client,
+    statements: Mutex<HashMap<String, Statement>>,
+    // Set when the connection may still be inside a transaction, so the pool drops it
+    broken: AtomicBool,
+}
+
+impl CachedClient {
+    pub fn new(client: Client) -> Self {
+        CachedClient {
+            client,
+            statements: Mutex::new(HashMap::default()),
+            broken: AtomicBool::new(false),
+        }
+    }
+
+    /// Keeps the connection from going back to the pool once released
+    pub fn mark_broken(&self) {
+        self.broken.store(true, Ordering::Relaxed);
+    }
+
+    pub async fn prepare_cached(&self, query: &str) -> Result<Statement, Error> {
+        let cached = self.statements.lock().unwrap().get(query).cloned();
+        if let Some(statement) = cached {
+            return Ok(statement);
+        }
+
+        let statement = self.client.prepare(query).await?;
+        self.statements
+            .lock()
+            .unwrap()
+            .insert(query.to_string(), statement.clone());
+        Ok(statement)
+    }
+}
+
+impl Deref for CachedClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+/// Wraps the bb8 Postgres manager to hand out `CachedClient`s
+pub struct CachedConnectionManager(PostgresConnectionManager<NoTls>);
+
+impl CachedConnectionManager {
+    pub fn new_from_stringlike<T: ToString>(params: T) -> Result<Self, Error> {
+        Ok(CachedConnectionManager(
+            PostgresConnectionManager::new_from_stringlike(params, NoTls)?,
+        ))
+    }
+}
+
+#[async_trait]
+impl ManageConnection for CachedConnectionManager {
+    type Connection = CachedClient;
+    type Error = Error;
+
+    async fn connect(&self) -> Result<Self::Connection, Self::Error> {
+        let client = self.0.connect().await?;
+        Ok(CachedClient::new(client))
+    }
+
+    async fn is_valid(&self, conn: &mut Self::Connection) -> Result<(), Self::Error> {
+        self.0.is_valid(&mut conn.client).await
+    }
+
+    fn has_broken(&self, conn: &mut Self::Connection) -> bool {
+        conn.broken.load(Ordering::Relaxed) || self.0.has_broken(&mut conn.client)
+    }
+}
@@ -0,0 +1,157 @@
+use log::info;
+
+use crate::{
+    constants::{get_sql_script, Migration, GLOBAL_MIGRATIONS},
+    errors::DatabaseError,
+    structures::{Document, FromPostgresRow, SQLEvent},
+};
+
+use super::{DBType, PooledTransaction};
+
+/// Migration recorded in the `schema_migrations` table
+pub struct AppliedMigration {
+    pub version: i32,
+    pub name: String,
+    pub checksum: String,
+}
+
+impl FromPostgresRow for AppliedMigration {
+    fn from_pg_row(r: tokio_postgres::Row) -> Self {
+        AppliedMigration {
+            version: r.get(0),
+            name: r.get(1),
+            checksum: r.get(2),
+        }
+    }
+}
+
+/// ## Migrator
+/// Applies and reverts the versioned migrations in `src/migrations`. Every
+/// migration runs in its own transaction together with its bookkeeping row
+/// in `schema_migrations`.
+pub struct Migrator {
+    pool: DBType,
+    migrations: &'static [Migration],
+}
+
+impl Migrator {
+    pub fn new(pool: DBType) -> Self {
+        Migrator {
+            pool,
+            migrations: &GLOBAL_MIGRATIONS,
+        }
+    }
+
+    pub fn migrations(&self) -> &[Migration] {
+        self.migrations
+    }
+
+    pub async fn applied(&self) -> Result<Vec<AppliedMigration>, DatabaseError> {
+        let client = self.pool.get().await?;
+        client
+            .batch_execute(&get_sql_script(
+                Document::GENERAL,
+                SQLEvent::CreateTable("SchemaMigrations".to_string()),
+            ))
+            .await?;
+        let rows = client
+            .query(
+                &get_sql_script(Document::SchemaMigrations, SQLEvent::Select("applied")),
+                &[],
+            )
+            .await?;
+
+        let applied = rows
+            .into_iter()
+            .map(AppliedMigration::from_pg_row)
+            .collect::<Vec<AppliedMigration>>();
+        self.verify(&applied)?;
+        Ok(applied)
+    }
+
+    /// Apply pending migrations up to `target`, or all of them
+    pub async fn up(&self, target: Option<i32>) -> Result<(), DatabaseError> {
+        let applied = self.applied().await?;
+        for migration in self.migrations.iter() {
+            if target.is_some_and(|t| migration.version > t)
+                || applied.iter().any(|a| a.version == migration.version)
+            {
+                continue;
+            }
+
+            info!(
+                "POSTGRES: Applying migration {} {}",
+                migration.version, migration.name
+            );
+            let tx = PooledTransaction::begin(self.pool.get_owned().await?).await?;
+            tx.batch_execute(&migration.up).await?;
+            tx.execute(
+                &get_sql_script(Document::SchemaMigrations, SQLEvent::Insert),
+                &[&migration.version, &migration.name, &migration.checksum],
+            )
+            .await?;
+            tx.commit().await?;
+        }
+        Ok(())
+    }
+
+    /// Revert applied migrations newer than `target`, or only the latest one
+    pub async fn down(&self, target: Option<i32>) -> Result<(), DatabaseError> {
+        let applied = self.applied().await?;
+        let target = match target {
+            Some(t) => t,
+            None => match applied.len() {
+                0 => return Ok(()),
+                n if n > 1 => applied[n - 2].version,
+                _ => 0,
+            },
+        };
+
+        for applied_migration in applied.iter().rev() {
+            if applied_migration.version <= target {
+                break;
+            }
+            // `verify` guarantees every applied migration is on disk
+            let migration = self.find(applied_migration.version).unwrap();
+
+            info!(
+                "POSTGRES: Reverting migration {} {}",
+                migration.version, migration.name
+            );
+            let tx = PooledTransaction::begin(self.pool.get_owned().await?).await?;
+            tx.batch_execute(&migration.down).await?;
+            tx.execute(
+                &get_sql_script(Document::SchemaMigrations, SQLEvent::Delete),
+                &[&migration.version],
+            )
+            .await?;
+            tx.commit().await?;
+        }
+        Ok(())
+    }
+
+    fn find(&self, version: i32) -> Option<&Migration> {
+        self.migrations.iter().find(|m| m.version == version)
+    }
+
+    fn verify(&self, applied: &[AppliedMigration]) -> Result<(), DatabaseError> {
+        for applied_migration in applied {
+            match self.find(applied_migration.version) {
+                None => {
+                    return Err(DatabaseError::Migration(format!(
+                        "migration {} {} is applied but missing on disk",
+                        applied_migration.version, applied_migration.name
+                    )))
+                }
+                Some(m) if m.checksum != applied_migration.checksum => {
+                    return Err(DatabaseError::Migration(format!(
+                        "migration {} {} was modified after being applied",
+                        m.version, m.name
+                    )))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
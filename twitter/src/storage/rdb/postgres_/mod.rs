@@ -1,75 +1,63 @@
+mod client;
+mod migrator;
 mod tx;
 mod ty;
 
-use std::cell::Cell;
-
 use async_trait::async_trait;
+pub use client::*;
 use log::info;
-pub use tx::*;
+pub use migrator::*;
 pub use ty::*;
 
 use crate::{
-    constants::get_sql_script,
     errors::DatabaseError,
-    structures::{DBTransaction, DatabaseAdapter, DatabaseType, Document, ImplDatabase, SQLEvent},
+    structures::{DBTransaction, DatabaseAdapter, DatabaseType, ImplDatabase, PoolConfig},
 };
-use tokio_postgres::{Client, NoTls};
 
 pub struct PostgresAdapter(DatabaseAdapter<DBType>);
 
 impl PostgresAdapter {
     impl_new_database!(DBType);
 
-    pub fn client(&mut self) -> Result<&mut Client, DatabaseError> {
-        let c = &mut self.0.db_instance;
-        return Ok(c.as_mut().get_mut());
+    pub fn pool(&self) -> &DBType {
+        &self.0.db_instance
+    }
+
+    pub async fn build_pool(
+        connection_str: &str,
+        pool_config: &PoolConfig,
+    ) -> Result<DBType, DatabaseError> {
+        let manager = CachedConnectionManager::new_from_stringlike(connection_str)?;
+        Ok(pool_config.builder().build(manager).await?)
     }
 
     pub async fn connect(
         connection_str: &str,
         auto_reset: bool,
+        pool_config: &PoolConfig,
     ) -> Result<PostgresAdapter, DatabaseError> {
-        let (client, connection) = tokio_postgres::connect(connection_str, NoTls).await?;
+        let pool = Self::build_pool(connection_str, pool_config).await?;
         info!("POSTGRES: Connecting and initializing...");
 
-        // Waiting for connection to settle
-        tokio::spawn(async move {
-            if let Err(e) = connection.await {
-                eprintln!("connection error: {}", e);
-            }
-        });
-
+        let migrator = Migrator::new(pool.clone());
         if auto_reset {
-            info!("POSTGRES: Dropping existing tables...");
-            client
-                .batch_execute(&get_sql_script(Document::GENERAL, SQLEvent::Reset))
-                .await?;
-            // Create tables
-            for table_name in vec!["Tweets", "Follows"].iter() {
-                client
-                    .batch_execute(&get_sql_script(
-                        Document::GENERAL,
-                        SQLEvent::CreateTable(table_name.to_string()),
-                    ))
-                    .await?;
-            }
-            // Create secondary indexing
-            client
-                .batch_execute(&get_sql_script(Document::GENERAL, SQLEvent::CreateIndices))
-                .await?;
+            info!("POSTGRES: Reverting all migrations...");
+            migrator.down(Some(0)).await?;
         }
+        // Bring the schema up to date without touching existing data
+        migrator.up(None).await?;
 
         info!("POSTGRES: Connect and successfully initialize database");
 
         Ok(PostgresAdapter(DatabaseAdapter::<DBType>::new(
             connection_str.to_string(),
-            Box::new(Cell::new(client)),
+            pool,
             DatabaseType::RelationalStore,
         )?))
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl ImplDatabase for PostgresAdapter {
     type Transaction = PostgresTransaction;
 
@@ -77,16 +65,10 @@ impl ImplDatabase for PostgresAdapter {
         &self.0.connection_str
     }
 
-    async fn transaction(&mut self, w: bool) -> Result<PostgresTransaction, DatabaseError> {
-        let db = self.client()?;
-        let tx = db.transaction().await.unwrap();
-        let longer_lifetime_tx = unsafe { extend_tx_lifetime(tx) };
-        Ok(DBTransaction::<TxType>::new(longer_lifetime_tx, w, false).unwrap())
+    async fn transaction(&self, w: bool) -> Result<PostgresTransaction, DatabaseError> {
+        // Every transaction leases its own connection from the pool
+        let conn = self.pool().get_owned().await?;
+        let tx = PooledTransaction::begin(conn).await?;
+        Ok(DBTransaction::<TxType>::new(tx, w, false).unwrap())
     }
 }
-
-unsafe fn extend_tx_lifetime(
-    tx: tokio_postgres::Transaction<'_>,
-) -> tokio_postgres::Transaction<'static> {
-    std::mem::transmute::<tokio_postgres::Transaction<'_>, tokio_postgres::Transaction<'static>>(tx)
-}
@@ -1,14 +1,17 @@
 use async_trait::async_trait;
-use tokio_postgres::types::ToSql;
+use futures::pin_mut;
+use tokio_postgres::{
+    binary_copy::BinaryCopyInWriter,
+    types::{ToSql, Type},
+};
 
 use crate::{
-    constants::get_sql_script,
+    constants::{get_sql_script, has_sql_script},
     errors::DatabaseError,
     misc::{Arg, Key},
     structures::{
         DBTransaction, FromPostgresRow, KeywordBucket, SQLEvent, SimpleTransaction, SuperValue,
     },
-    utils::sss,
 };
 
 use super::ty::TxType;
@@ -59,7 +62,7 @@ impl SimpleTransaction for DBTransaction<TxType> {
         &mut self,
         key: K,
         args: A,
-        _keywords: KeywordBucket,
+        keywords: KeywordBucket,
     ) -> Result<(), DatabaseError>
     where
         K: Into<Key> + Send,
@@ -83,11 +86,23 @@ impl SimpleTransaction for DBTransaction<TxType> {
             .map(|x| -> PostgresArgType { x.as_ref() })
             .collect::<Vec<PostgresArgType>>();
 
-        tx.execute(
-            &get_sql_script(key.clone(), SQLEvent::Insert),
-            &pg_params_ref,
-        )
-        .await?;
+        // Tagged writes must match an existing row, either the one they insert against, e.g. the
+        // replied tweet, or the one they update, e.g. the profile. Otherwise nothing is written
+        let tag = keywords.get("tag");
+        let event = match tag.as_deref() {
+            Some(tag) if has_sql_script(key.clone(), SQLEvent::InsertBy(tag)) => {
+                SQLEvent::InsertBy(tag)
+            }
+            Some(tag) => SQLEvent::Update(tag),
+            None => SQLEvent::Insert,
+        };
+        let is_tagged = tag.is_some();
+        let rows = tx
+            .execute(&get_sql_script(key.clone(), event), &pg_params_ref)
+            .await?;
+        if is_tagged && rows == 0 {
+            return Err(DatabaseError::TxnKeyNotFound);
+        }
 
         Ok(())
     }
@@ -108,23 +123,116 @@ impl SimpleTransaction for DBTransaction<TxType> {
         let mut guarded_tx = self.tx.lock().await;
         let tx = guarded_tx.as_mut().unwrap();
 
-        let mut batch_params: Vec<Box<dyn ToSql + Send + Sync>> = vec![];
-        for arg_item in args {
-            let mut pg_params = to_pg_prams(arg_item.into());
-            batch_params.append(&mut pg_params);
+        let rows = args
+            .into_iter()
+            .map(|arg_item| to_pg_prams(arg_item.into()))
+            .collect::<Vec<Vec<PostgresReturnType>>>();
+        let columns = match rows.first() {
+            Some(row) => row.len(),
+            None => return Ok(()),
+        };
+
+        let k = key.into();
+        // Split the batch so one statement stays under the bind parameter limit
+        for chunk in rows.chunks(rows_per_batch(columns)) {
+            let script = batch_insert_script(k.clone(), chunk.len(), columns);
+            let statement = tx.prepare_cached(&script).await?;
+            let pg_params_ref = chunk
+                .iter()
+                .flatten()
+                .map(|x| -> PostgresArgType { x.as_ref() })
+                .collect::<Vec<PostgresArgType>>();
+
+            tx.execute(&statement, &pg_params_ref).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn bulk_set<K, A>(&mut self, key: K, args: Vec<A>) -> Result<(), DatabaseError>
+    where
+        K: Into<Key> + Send,
+        A: Into<Arg> + Send,
+    {
+        if self.closed() {
+            return Err(DatabaseError::TxFinished);
+        }
+
+        if !self.writable {
+            return Err(DatabaseError::TxReadonly);
+        }
+
+        let guarded_tx = self.tx.lock().await;
+        let tx = guarded_tx.as_ref().unwrap();
+
+        let args = args
+            .into_iter()
+            .map(|arg_item| arg_item.into())
+            .collect::<Vec<Arg>>();
+        let types = match args.first() {
+            Some(row) => row.iter().map(to_pg_type).collect::<Vec<Type>>(),
+            None => return Ok(()),
+        };
+        let rows = args.into_iter().map(to_pg_prams).collect::<Vec<_>>();
+
+        // Stream every row through a single binary COPY
+        let sink = tx
+            .copy_in(&get_sql_script(key.into(), SQLEvent::Copy))
+            .await?;
+        let writer = BinaryCopyInWriter::new(sink, &types);
+        pin_mut!(writer);
+        for row in rows.iter() {
+            let pg_params_ref = row
+                .iter()
+                .map(|x| -> PostgresArgType { x.as_ref() })
+                .collect::<Vec<PostgresArgType>>();
+            writer.as_mut().write(&pg_params_ref).await?;
+        }
+        writer.finish().await?;
+
+        Ok(())
+    }
+
+    async fn del<K, A>(
+        &mut self,
+        key: K,
+        args: A,
+        keywords: KeywordBucket,
+    ) -> Result<(), DatabaseError>
+    where
+        K: Into<Key> + Send,
+        A: Into<Arg> + Send,
+    {
+        if self.closed() {
+            return Err(DatabaseError::TxFinished);
+        }
+
+        if !self.writable {
+            return Err(DatabaseError::TxReadonly);
         }
 
-        let pg_params_ref = batch_params
+        let mut guarded_tx = self.tx.lock().await;
+        let tx = guarded_tx.as_mut().unwrap();
+        let key = key.into();
+
+        let pg_params = to_pg_prams(args.into());
+        let pg_params_ref = pg_params
             .iter()
             .map(|x| -> PostgresArgType { x.as_ref() })
             .collect::<Vec<PostgresArgType>>();
 
-        let k = key.into();
-        tx.execute(
-            &get_sql_script(k.clone(), SQLEvent::BatchInsert),
-            &pg_params_ref,
-        )
-        .await?;
+        // Tagged deletes narrow down the rows, e.g. only pending follow requests
+        let tag = keywords.get("tag");
+        let event = match tag.as_deref() {
+            Some(tag) => SQLEvent::DeleteBy(tag),
+            None => SQLEvent::Delete,
+        };
+        let rows = tx
+            .execute(&get_sql_script(key, event), &pg_params_ref)
+            .await?;
+        if rows == 0 {
+            return Err(DatabaseError::TxnKeyNotFound);
+        }
 
         Ok(())
     }
@@ -148,16 +256,16 @@ impl SimpleTransaction for DBTransaction<TxType> {
         let tx = guarded_tx.as_ref().unwrap();
         let (key, args) = (key.into(), args.into());
 
-        let pg_params = to_pg_prams(args.into());
+        let pg_params = to_pg_prams(args);
         let pg_params_ref = pg_params
             .iter()
             .map(|x| -> PostgresArgType { x.as_ref() })
             .collect::<Vec<PostgresArgType>>();
 
-        let script = sss(keywords.get("tag").unwrap());
+        let tag = keywords.get("tag").unwrap();
         let rows = tx
             .query(
-                &get_sql_script(key.clone(), SQLEvent::Select(script)),
+                &get_sql_script(key.clone(), SQLEvent::Select(&tag)),
                 &pg_params_ref,
             )
             .await?;
@@ -171,7 +279,33 @@ impl SimpleTransaction for DBTransaction<TxType> {
     }
 }
 
-type PostgresReturnType = Box<(dyn ToSql + Send + Sync + 'static)>;
+const MAX_BIND_PARAMETERS: usize = u16::MAX as usize;
+
+/// Largest number of rows of `columns` values one batch insert statement can bind
+fn rows_per_batch(columns: usize) -> usize {
+    MAX_BIND_PARAMETERS / columns
+}
+
+/// Appends `rows` tuples of `columns` placeholders to the batch insert script,
+/// e.g. `($1, $2), ($3, $4)` for two rows of two columns
+fn batch_insert_script(key: Key, rows: usize, columns: usize) -> String {
+    let values = (0..rows)
+        .map(|row| {
+            let placeholders = (1..=columns)
+                .map(|column| format!("${}", row * columns + column))
+                .collect::<Vec<String>>();
+            format!("({})", placeholders.join(", "))
+        })
+        .collect::<Vec<String>>();
+
+    format!(
+        "{} {}",
+        get_sql_script(key, SQLEvent::BatchInsert).trim_end(),
+        values.join(", ")
+    )
+}
+
+type PostgresReturnType = Box<dyn ToSql + Send + Sync + 'static>;
 fn to_pg_prams(params: Vec<SuperValue>) -> Vec<PostgresReturnType> {
     let mut result: Vec<PostgresReturnType> = vec![];
     for item in params {
@@ -187,7 +321,77 @@ fn to_pg_prams(params: Vec<SuperValue>) -> Vec<PostgresReturnType> {
                 }
             };
         }
-        param_convert!(String, Integer, BigInteger, SmallInteger, Char);
+        param_convert!(
+            String,
+            Integer,
+            BigInteger,
+            SmallInteger,
+            Char,
+            Bool,
+            Timestamp
+        );
     }
     result
 }
+
+fn to_pg_type(param: &SuperValue) -> Type {
+    match param {
+        SuperValue::String(_) => Type::VARCHAR,
+        SuperValue::Integer(_) => Type::INT4,
+        SuperValue::BigInteger(_) => Type::INT8,
+        SuperValue::SmallInteger(_) => Type::INT2,
+        SuperValue::Char(_) => Type::CHAR,
+        SuperValue::Bool(_) => Type::BOOL,
+        SuperValue::Timestamp(_) => Type::TIMESTAMP,
+        _ => unimplemented!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structures::Document;
+
+    const INSERT_TWEETS: &str = "INSERT INTO Tweets (user_id, tweet_text) VALUES";
+
+    #[test]
+    fn batch_insert_script_single_row() {
+        let script = batch_insert_script(Document::Tweets, 1, 2);
+        assert!(script.ends_with(&format!("{} ($1, $2)", INSERT_TWEETS)));
+    }
+
+    #[test]
+    fn batch_insert_script_numbers_placeholders_across_rows() {
+        let script = batch_insert_script(Document::Tweets, 3, 2);
+        assert!(script.ends_with(&format!("{} ($1, $2), ($3, $4), ($5, $6)", INSERT_TWEETS)));
+    }
+
+    #[test]
+    fn batch_insert_script_at_chunk_boundary() {
+        for columns in 1..=5 {
+            let rows = rows_per_batch(columns);
+            // A full chunk binds at most the limit, one more row would exceed it
+            assert!(rows * columns <= MAX_BIND_PARAMETERS);
+            assert!((rows + 1) * columns > MAX_BIND_PARAMETERS);
+        }
+        let rows = rows_per_batch(3);
+        let script = batch_insert_script(Document::Tweets, rows, 3);
+        assert_eq!(script.matches('(').count(), rows + 1);
+        assert!(script.ends_with(&format!(
+            "(${}, ${}, ${})",
+            MAX_BIND_PARAMETERS - 2,
+            MAX_BIND_PARAMETERS - 1,
+            MAX_BIND_PARAMETERS
+        )));
+    }
+
+    #[test]
+    fn rows_chunk_at_the_bind_parameter_limit() {
+        let rows = vec![[0; 2]; rows_per_batch(2) * 2 + 1];
+        let chunks = rows
+            .chunks(rows_per_batch(2))
+            .map(<[_]>::len)
+            .collect::<Vec<usize>>();
+        assert_eq!(chunks, vec![rows_per_batch(2), rows_per_batch(2), 1]);
+    }
+}
@@ -1,11 +1,79 @@
 extern crate tokio_postgres;
 
-use std::cell::Cell;
+use std::ops::Deref;
 
-use tokio_postgres::{Client, Transaction};
+use bb8::{Pool, PooledConnection};
 
-use crate::structures::DBTransaction;
+use crate::{errors::DatabaseError, structures::DBTransaction};
 
-pub type DBType = Box<Cell<Client>>;
-pub type TxType = Transaction<'static>;
+use super::{CachedClient, CachedConnectionManager};
+
+pub type ConnectionManager = CachedConnectionManager;
+pub type DBType = Pool<ConnectionManager>;
+pub type TxType = PooledTransaction;
 pub type PostgresTransaction = DBTransaction<TxType>;
+
+type PooledClient = PooledConnection<'static, ConnectionManager>;
+
+/// ## PooledTransaction
+/// Transaction that owns the pooled connection it runs on. `BEGIN` is sent
+/// when the connection is leased and the connection only goes back to the
+/// pool once the transaction is committed or rolled back.
+///
+/// The client is only reachable through `Deref`, so a second transaction
+/// can't be nested on the same connection. Concurrent transactions each
+/// lease their own connection.
+pub struct PooledTransaction {
+    conn: Option<PooledClient>,
+}
+
+impl PooledTransaction {
+    pub async fn begin(conn: PooledClient) -> Result<Self, DatabaseError> {
+        conn.batch_execute("BEGIN").await?;
+        Ok(PooledTransaction { conn: Some(conn) })
+    }
+
+    pub async fn commit(mut self) -> Result<(), DatabaseError> {
+        self.finish("COMMIT").await
+    }
+
+    pub async fn rollback(mut self) -> Result<(), DatabaseError> {
+        self.finish("ROLLBACK").await
+    }
+
+    async fn finish(&mut self, statement: &str) -> Result<(), DatabaseError> {
+        let conn = self.conn.as_ref().ok_or(DatabaseError::TxFinished)?;
+        conn.batch_execute(statement).await?;
+        // Only release the connection once the statement went through,
+        // otherwise `Drop` rolls it back before returning it to the pool
+        self.conn.take();
+        Ok(())
+    }
+}
+
+impl Deref for PooledTransaction {
+    type Target = CachedClient;
+
+    fn deref(&self) -> &Self::Target {
+        self.conn.as_ref().unwrap()
+    }
+}
+
+impl Drop for PooledTransaction {
+    fn drop(&mut self) {
+        // Never hand a connection with an open transaction back to the pool
+        if let Some(conn) = self.conn.take() {
+            match tokio::runtime::Handle::try_current() {
+                Ok(handle) => {
+                    handle.spawn(async move {
+                        if conn.batch_execute("ROLLBACK").await.is_err() {
+                            conn.mark_broken();
+                        }
+                    });
+                }
+                // Without a runtime to roll back on, the connection is closed instead
+                Err(_) => conn.mark_broken(),
+            }
+        }
+    }
+}
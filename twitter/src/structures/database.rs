@@ -9,6 +9,7 @@ pub enum DatabaseType {
     KeyValueStore,
 }
 
+#[allow(dead_code)]
 pub struct DatabaseAdapter<T> {
     pub connection_str: String,
     pub db_instance: T,
@@ -29,12 +30,13 @@ impl<T> DatabaseAdapter<T> {
     }
 }
 
-#[async_trait(?Send)]
-pub trait ImplDatabase {
+#[async_trait]
+pub trait ImplDatabase: Send + Sync {
     type Transaction: SimpleTransaction;
     // # Create new database transaction
     // Set `rw` default to false means readable but not readable
-    async fn transaction(&mut self, rw: bool) -> Result<Self::Transaction, DatabaseError>;
+    async fn transaction(&self, rw: bool) -> Result<Self::Transaction, DatabaseError>;
 
+    #[allow(dead_code)]
     fn connection(&self) -> &str;
 }
@@ -1,10 +1,22 @@
 use std::fmt::{Display, Formatter, Result as FormatResult};
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, Debug)]
 pub enum Document {
     GENERAL,
     Tweets,
     Follows,
+    Users,
+    Likes,
+    Retweets,
+    Hashtags,
+    Messages,
+    Blocks,
+    Mutes,
+    Bookmarks,
+    Lists,
+    ScheduledTweets,
+    SchemaMigrations,
 }
 
 impl From<Document> for String {
@@ -13,6 +25,17 @@ impl From<Document> for String {
             Document::GENERAL => "GENERAL".to_string(),
             Document::Tweets => "Tweets".to_string(),
             Document::Follows => "Follows".to_string(),
+            Document::Users => "Users".to_string(),
+            Document::Likes => "Likes".to_string(),
+            Document::Retweets => "Retweets".to_string(),
+            Document::Hashtags => "Hashtags".to_string(),
+            Document::Messages => "Messages".to_string(),
+            Document::Blocks => "Blocks".to_string(),
+            Document::Mutes => "Mutes".to_string(),
+            Document::Bookmarks => "Bookmarks".to_string(),
+            Document::Lists => "Lists".to_string(),
+            Document::ScheduledTweets => "ScheduledTweets".to_string(),
+            Document::SchemaMigrations => "SchemaMigrations".to_string(),
         }
     }
 }
@@ -1,30 +1,38 @@
 use std::fmt::{Display, Formatter, Result as FormatResult};
 
+/// Tags are borrowed, scripts are registered with static tags and looked up with the tag of
+/// each call
 #[derive(Clone)]
-pub enum SQLEvent {
+pub enum SQLEvent<'a> {
     CreateTable(String),
-    CreateIndices,
     Insert,
+    InsertBy(&'a str),
     BatchInsert,
-    Select(&'static str),
-    Reset,
+    Copy,
+    Select(&'a str),
+    Update(&'a str),
+    Delete,
+    DeleteBy(&'a str),
 }
 
-impl Display for SQLEvent {
+impl Display for SQLEvent<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
         write!(f, "{}", String::from(self.clone()))
     }
 }
 
-impl From<SQLEvent> for String {
-    fn from(event: SQLEvent) -> String {
+impl From<SQLEvent<'_>> for String {
+    fn from(event: SQLEvent<'_>) -> String {
         let event_str: String = match event {
             SQLEvent::Insert => "INSERT".to_string(),
+            SQLEvent::InsertBy(tag) => format!("INSERT_{}", tag).to_string(),
             SQLEvent::Select(tag) => format!("SELECT_{}", tag).to_string(),
             SQLEvent::CreateTable(name) => format!("CREATE_TABLE_{}", name).to_string(),
-            SQLEvent::Reset => "RESET".to_string(),
+            SQLEvent::Update(tag) => format!("UPDATE_{}", tag).to_string(),
+            SQLEvent::Delete => "DELETE".to_string(),
+            SQLEvent::DeleteBy(tag) => format!("DELETE_{}", tag).to_string(),
             SQLEvent::BatchInsert => "BATCH_INSERT".to_string(),
-            SQLEvent::CreateIndices => "CREATE_INDICES".to_string(),
+            SQLEvent::Copy => "COPY".to_string(),
         };
         event_str
     }
@@ -2,6 +2,7 @@ mod database;
 mod document;
 mod events;
 mod keyword;
+mod pool;
 mod tx;
 mod value;
 
@@ -9,5 +10,6 @@ pub use database::*;
 pub use document::*;
 pub use events::*;
 pub use keyword::*;
+pub use pool::*;
 pub use tx::*;
 pub use value::*;
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use bb8::{Builder, ManageConnection};
+
+/// ## PoolConfig
+/// Connection pool settings shared by every database adapter
+/// ---
+/// | Field              | Description                                          |
+/// | ------------------ | ---------------------------------------------------- |
+/// | max_size           | Maximum number of connections held by the pool       |
+/// | min_idle           | Idle connections the pool tries to keep warm         |
+/// | connection_timeout | How long a checkout waits before giving up           |
+/// | idle_timeout       | Idle connections older than this are closed          |
+/// | max_lifetime       | Connections older than this are recycled             |
+/// | test_on_check_out  | Run a health check before handing out a connection   |
+/// ---
+#[derive(Clone, Debug)]
+pub struct PoolConfig {
+    pub max_size: u32,
+    pub min_idle: Option<u32>,
+    pub connection_timeout: Duration,
+    pub idle_timeout: Option<Duration>,
+    pub max_lifetime: Option<Duration>,
+    pub test_on_check_out: bool,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 10,
+            min_idle: None,
+            connection_timeout: Duration::from_secs(30),
+            idle_timeout: Some(Duration::from_secs(10 * 60)),
+            max_lifetime: Some(Duration::from_secs(30 * 60)),
+            test_on_check_out: true,
+        }
+    }
+}
+
+impl PoolConfig {
+    pub fn builder<M: ManageConnection>(&self) -> Builder<M> {
+        Builder::new()
+            .max_size(self.max_size)
+            .min_idle(self.min_idle)
+            .connection_timeout(self.connection_timeout)
+            .idle_timeout(self.idle_timeout)
+            .max_lifetime(self.max_lifetime)
+            .test_on_check_out(self.test_on_check_out)
+    }
+}
@@ -12,10 +12,12 @@ pub struct DBTransaction<T>
 where
     T: 'static,
 {
+    #[allow(dead_code)]
     pub debug: bool,
     pub tx: Arc<Mutex<Option<T>>>,
     pub ok: bool,
     pub writable: bool,
+    #[allow(dead_code)]
     pub readable: bool,
 }
 
@@ -40,6 +42,7 @@ pub trait SimpleTransaction {
     fn closed(&self) -> bool;
 
     // Cancel a transaction
+    #[allow(dead_code)]
     async fn cancel(&mut self) -> Result<(), DatabaseError>;
 
     // Commit a transaction
@@ -67,6 +70,12 @@ pub trait SimpleTransaction {
         K: Into<Key> + Send,
         A: Into<Arg> + Send;
 
+    /// Bulk load rows, bypassing per-row inserts where the backend allows it
+    async fn bulk_set<K, A>(&mut self, key: K, args: Vec<A>) -> Result<(), DatabaseError>
+    where
+        K: Into<Key> + Send,
+        A: Into<Arg> + Send;
+
     async fn get<K, A, V>(
         &self,
         key: K,
@@ -86,6 +95,14 @@ pub trait SimpleTransaction {
     //     val: V,
     // ) -> Result<(), DatabaseError>;
 
-    // /// Delete a key
-    // async fn del<K: Into<Key> + Send>(&mut self, cf: CF, key: K) -> Result<(), DatabaseError>;
+    /// Delete a key from the database
+    async fn del<K, A>(
+        &mut self,
+        key: K,
+        args: A,
+        keywords: KeywordBucket,
+    ) -> Result<(), DatabaseError>
+    where
+        K: Into<Key> + Send,
+        A: Into<Arg> + Send;
 }
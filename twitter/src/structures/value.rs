@@ -21,6 +21,7 @@ use crate::errors::DatabaseError;
 /// | &str/String | VARCHAR, CHAR(n), TEXT, CITEXT, NAME |
 /// |             | LTREE, LQUERY, LTXTQUERY             |
 /// ---
+#[allow(dead_code)]
 pub enum SuperValue {
     Bool(bool),
     Char(i8),
@@ -34,8 +35,9 @@ pub enum SuperValue {
 }
 
 #[doc(hidden)]
+#[allow(dead_code)]
 pub trait BorrowFromSuperValue: Sized {
-    fn from_super_value<'a>(v: &'a SuperValue) -> Result<&'a Self, DatabaseError>;
+    fn from_super_value(v: &SuperValue) -> Result<&Self, DatabaseError>;
 }
 
 pub trait FromPostgresRow {
@@ -45,3 +47,28 @@ pub trait FromPostgresRow {
 pub trait FromRedisValue {
     fn from_redis_value(v: String) -> Self;
 }
+
+/// Single column results such as ids and counters
+impl FromPostgresRow for i32 {
+    fn from_pg_row(r: Row) -> Self {
+        r.get(0)
+    }
+}
+
+impl FromRedisValue for i32 {
+    fn from_redis_value(v: String) -> Self {
+        v.parse().unwrap_or_default()
+    }
+}
+
+impl FromPostgresRow for i64 {
+    fn from_pg_row(r: Row) -> Self {
+        r.get(0)
+    }
+}
+
+impl FromRedisValue for i64 {
+    fn from_redis_value(v: String) -> Self {
+        v.parse().unwrap_or_default()
+    }
+}
@@ -3,7 +3,7 @@ use colored::Colorize;
 pub fn log_stage(stage: &'static str, title: &'static str) {
     println!(
         "{} : {}",
-        format!("{}", stage).yellow(),
-        format!("{}", title).bold()
+        stage.to_string().yellow(),
+        title.to_string().bold()
     );
 }
@@ -0,0 +1,187 @@
+/// Longest handle a user can register
+const MAX_HANDLE_LENGTH: usize = 15;
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Lowercased words following `sigil`, without duplicates. The sigil must not follow a
+/// word character, e.g. `a@b` is an email address rather than a mention
+fn parse_tokens(text: &str, sigil: char) -> Vec<String> {
+    let chars = text.chars().collect::<Vec<char>>();
+    let mut tokens: Vec<String> = vec![];
+    for (i, c) in chars.iter().enumerate() {
+        if *c != sigil || (i > 0 && is_word_char(chars[i - 1])) {
+            continue;
+        }
+        let token = chars[i + 1..]
+            .iter()
+            .take_while(|c| is_word_char(**c))
+            .collect::<String>()
+            .to_lowercase();
+        if !token.is_empty() && !tokens.contains(&token) {
+            tokens.push(token);
+        }
+    }
+    tokens
+}
+
+/// Lowercased `@handle` mentions of a tweet text. Mirrors the pattern of the Postgres
+/// mentions trigger, the handle must be at most 15 characters long
+pub fn parse_mentions(text: &str) -> Vec<String> {
+    parse_tokens(text, '@')
+        .into_iter()
+        .filter(|handle| handle.len() <= MAX_HANDLE_LENGTH)
+        .collect()
+}
+
+/// Lowercased `#hashtags` of a tweet text. Mirrors the pattern of the Postgres hashtags
+/// trigger, a hashtag needs at least one letter so `#1` is not one
+pub fn parse_hashtags(text: &str) -> Vec<String> {
+    parse_tokens(text, '#')
+        .into_iter()
+        .filter(|tag| tag.chars().any(|c| c.is_ascii_alphabetic()))
+        .collect()
+}
+
+/// Lowercased search terms of a text, without duplicates. Terms are runs of letters and
+/// digits, single characters are too common to be worth indexing
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut terms: Vec<String> = vec![];
+    for term in text.split(|c: char| !c.is_alphanumeric()) {
+        let term = term.to_lowercase();
+        if term.chars().count() > 1 && !terms.contains(&term) {
+            terms.push(term);
+        }
+    }
+    terms
+}
+
+/// Splits a search query on the `OR` keyword into groups of terms that must all match,
+/// e.g. `rust tokio OR redis` is `(rust AND tokio) OR redis`
+pub fn parse_search_query(query: &str) -> Vec<Vec<String>> {
+    query
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .split(|word| *word == "OR")
+        .map(|group| tokenize(&group.join(" ")))
+        .filter(|group| !group.is_empty())
+        .collect()
+}
+
+/// Renders a search query in its normalized form, e.g. `Rust, tokio OR redis!` is
+/// `rust tokio OR redis`. Both databases search with the normalized query
+pub fn normalize_search_query(query: &str) -> String {
+    parse_search_query(query)
+        .iter()
+        .map(|group| group.join(" "))
+        .collect::<Vec<String>>()
+        .join(" OR ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mentions_cases() {
+        let cases: &[(&str, &[&str])] = &[
+            ("hi @Alice!", &["alice"]),
+            ("(@bob), @carol.", &["bob", "carol"]),
+            ("@dave @DAVE @dave", &["dave"]),
+            ("@@x", &["x"]),
+            ("a@b.com", &[]),
+            ("trailing @", &[]),
+            ("@", &[]),
+            ("@_under_score", &["_under_score"]),
+            ("@fifteen_chars_x", &["fifteen_chars_x"]),
+            ("@sixteen_chars_xx", &[]),
+            ("@josé", &["jos"]),
+            ("é@bob", &["bob"]),
+            ("no mentions", &[]),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse_mentions(text), *expected, "text: {:?}", text);
+        }
+    }
+
+    #[test]
+    fn parse_hashtags_cases() {
+        let cases: &[(&str, &[&str])] = &[
+            ("#Rust is #fun", &["rust", "fun"]),
+            ("#rust #RUST", &["rust"]),
+            ("#1", &[]),
+            ("#2023goals", &["2023goals"]),
+            ("#_", &[]),
+            ("end #", &[]),
+            ("#", &[]),
+            ("a#b", &[]),
+            ("##double", &["double"]),
+            ("(#paren), #dot.", &["paren", "dot"]),
+            ("#café", &["caf"]),
+            ("no tags", &[]),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse_hashtags(text), *expected, "text: {:?}", text);
+        }
+    }
+
+    #[test]
+    fn tokenize_cases() {
+        let cases: &[(&str, &[&str])] = &[
+            ("Hello, World!", &["hello", "world"]),
+            ("a b cd", &["cd"]),
+            ("Rust rust RUST", &["rust"]),
+            ("don't", &["don"]),
+            ("state-of-the-art", &["state", "of", "the", "art"]),
+            ("#rust @tokio", &["rust", "tokio"]),
+            ("Café ÜBER", &["café", "über"]),
+            ("web3 2024", &["web3", "2024"]),
+            ("", &[]),
+            ("  ,.! ", &[]),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(tokenize(text), *expected, "text: {:?}", text);
+        }
+    }
+
+    #[test]
+    fn parse_search_query_cases() {
+        let cases: &[(&str, &[&[&str]])] = &[
+            ("rust tokio OR redis", &[&["rust", "tokio"], &["redis"]]),
+            ("Rust, Tokio!", &[&["rust", "tokio"]]),
+            ("OR rust", &[&["rust"]]),
+            ("rust OR", &[&["rust"]]),
+            ("rust OR OR redis", &[&["rust"], &["redis"]]),
+            ("OR", &[]),
+            ("", &[]),
+            ("rust or redis", &[&["rust", "or", "redis"]]),
+            ("rust ORredis", &[&["rust", "orredis"]]),
+            ("-tokio \"rust lang\"", &[&["tokio", "rust", "lang"]]),
+            ("a OR rust", &[&["rust"]]),
+        ];
+        for (query, expected) in cases {
+            assert_eq!(parse_search_query(query), *expected, "query: {:?}", query);
+        }
+    }
+
+    #[test]
+    fn normalize_search_query_cases() {
+        let cases: &[(&str, &str)] = &[
+            ("Rust, tokio OR redis!", "rust tokio OR redis"),
+            ("OR rust OR", "rust"),
+            ("rust or redis", "rust or redis"),
+            ("", ""),
+        ];
+        for (query, expected) in cases {
+            assert_eq!(
+                normalize_search_query(query),
+                *expected,
+                "query: {:?}",
+                query
+            );
+            // Normalizing twice is a no-op, the databases parse the normalized query again
+            assert_eq!(normalize_search_query(expected), *expected);
+        }
+    }
+}